@@ -0,0 +1,373 @@
+//! Implementation of `#[derive(ToLino, FromLino)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Generics, Ident, Index, LitStr, Member, Result,
+};
+
+#[derive(Default)]
+struct Options {
+    rename: Option<String>,
+    id: bool,
+    flatten: bool,
+    skip: bool,
+}
+
+impl Options {
+    fn parse(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut options = Options::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("lino")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    if name.value().is_empty() {
+                        return Err(Error::new(name.span(), "names cannot be empty"));
+                    }
+                    options.rename = Some(name.value());
+                } else if meta.path.is_ident("id") {
+                    options.id = true;
+                } else if meta.path.is_ident("flatten") {
+                    options.flatten = true;
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error("expected `rename`, `id`, `flatten` or `skip`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+struct Field {
+    member: Member,
+    // Binding used when destructuring enum variants
+    binding: Ident,
+    name: String,
+    options: Options,
+}
+
+fn fields(fields: &Fields, allow_id: bool) -> Result<Vec<Field>> {
+    let mut result = vec![];
+    let mut id = None;
+    for (index, field) in fields.iter().enumerate() {
+        let options = Options::parse(&field.attrs)?;
+        let span = field.ident.as_ref().map_or_else(Span::call_site, Ident::span);
+        let named = field.ident.is_some();
+        if options.id {
+            if !allow_id || !named {
+                return Err(Error::new(span, "`id` is only supported on fields of structs with named fields"));
+            }
+            if options.flatten || options.skip {
+                return Err(Error::new(span, "`id` cannot be combined with `flatten` or `skip`"));
+            }
+            if id.replace(index).is_some() {
+                return Err(Error::new(span, "only one field can be the id"));
+            }
+        }
+        if options.flatten && !named {
+            return Err(Error::new(span, "`flatten` is only supported on named fields"));
+        }
+        if (options.rename.is_some() || options.skip) && !named {
+            return Err(Error::new(span, "`rename` and `skip` are only supported on named fields"));
+        }
+        let (member, binding) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), format_ident!("__{}", ident)),
+            None => (Member::Unnamed(Index::from(index)), format_ident!("__{}", index)),
+        };
+        let name = options
+            .rename
+            .clone()
+            .unwrap_or_else(|| field.ident.as_ref().map(|ident| ident.unraw().to_string()).unwrap_or_default());
+        result.push(Field {
+            member,
+            binding,
+            name,
+            options,
+        });
+    }
+    Ok(result)
+}
+
+fn variant_name(variant: &syn::Variant) -> Result<String> {
+    let options = Options::parse(&variant.attrs)?;
+    if options.id || options.flatten || options.skip {
+        return Err(Error::new(variant.ident.span(), "only `rename` is supported on variants"));
+    }
+    Ok(options.rename.unwrap_or_else(|| variant.ident.unraw().to_string()))
+}
+
+fn check_container(input: &DeriveInput) -> Result<()> {
+    let options = Options::parse(&input.attrs)?;
+    if options.rename.is_some() || options.id || options.flatten || options.skip {
+        return Err(Error::new(
+            input.ident.span(),
+            "`#[lino(...)]` attributes are supported on fields and variants",
+        ));
+    }
+    Ok(())
+}
+
+// Adds `bound` to every type parameter
+fn with_bound(generics: &Generics, bound: TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(#bound));
+        }
+    }
+    generics
+}
+
+// Values written for named fields, reading each through `access`
+fn write_fields(fields: &[Field], access: impl Fn(&Field) -> TokenStream) -> TokenStream {
+    let pushes = fields.iter().filter(|field| !field.options.skip && !field.options.id).map(|field| {
+        let value = access(field);
+        if field.options.flatten {
+            quote!(__values.extend(::links_notation::convert::splice(#value));)
+        } else {
+            let name = &field.name;
+            quote!(__values.push(::links_notation::convert::field(#name, #value));)
+        }
+    });
+    quote! {
+        #[allow(unused_mut)]
+        let mut __values = ::std::vec::Vec::new();
+        #(#pushes)*
+    }
+}
+
+fn id_of(fields: &[Field], access: impl Fn(&Field) -> TokenStream) -> TokenStream {
+    match fields.iter().find(|field| field.options.id) {
+        Some(field) => {
+            let value = access(field);
+            quote!(::core::option::Option::Some(::links_notation::convert::to_id(#value)))
+        }
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+pub fn expand_to_lino(input: DeriveInput) -> Result<TokenStream> {
+    check_container(&input)?;
+    let ident = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = fields(&data.fields, true)?;
+            match &data.fields {
+                Fields::Named(_) => {
+                    let access = |field: &Field| {
+                        let member = &field.member;
+                        quote!(&self.#member)
+                    };
+                    let values = write_fields(&fields, access);
+                    let id = id_of(&fields, access);
+                    quote! {
+                        #values
+                        ::links_notation::LiNo::Link { id: #id, values: __values }
+                    }
+                }
+                Fields::Unnamed(_) if fields.len() == 1 => quote!(::links_notation::ToLino::to_lino(&self.0)),
+                Fields::Unnamed(_) => {
+                    let members = fields.iter().map(|field| &field.member);
+                    quote! {
+                        ::links_notation::LiNo::Link {
+                            id: ::core::option::Option::None,
+                            values: ::std::vec![#(::links_notation::ToLino::to_lino(&self.#members)),*],
+                        }
+                    }
+                }
+                Fields::Unit => quote!(::links_notation::ToLino::to_lino(&())),
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let name = variant_name(variant)?;
+                    let variant_ident = &variant.ident;
+                    let fields = fields(&variant.fields, false)?;
+                    let bindings: Vec<_> = fields.iter().map(|field| &field.binding).collect();
+                    Ok(match &variant.fields {
+                        Fields::Unit => quote! {
+                            Self::#variant_ident => ::links_notation::LiNo::Ref(::std::string::String::from(#name))
+                        },
+                        Fields::Unnamed(_) => quote! {
+                            Self::#variant_ident(#(#bindings),*) => ::links_notation::LiNo::Link {
+                                id: ::core::option::Option::Some(::std::string::String::from(#name)),
+                                values: ::std::vec![#(::links_notation::ToLino::to_lino(#bindings)),*],
+                            }
+                        },
+                        Fields::Named(_) => {
+                            let members = fields.iter().map(|field| &field.member);
+                            let values = write_fields(&fields, |field| {
+                                let binding = &field.binding;
+                                quote!(#binding)
+                            });
+                            quote! {
+                                #[allow(unused_variables)]
+                                Self::#variant_ident { #(#members: #bindings),* } => {
+                                    #values
+                                    ::links_notation::LiNo::Link {
+                                        id: ::core::option::Option::Some(::std::string::String::from(#name)),
+                                        values: __values,
+                                    }
+                                }
+                            }
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(_) => return Err(Error::new(ident.span(), "unions cannot be converted to links")),
+    };
+    let generics = with_bound(&input.generics, quote!(::links_notation::ToLino));
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::links_notation::ToLino for #ident #type_generics #where_clause {
+            fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+                #body
+            }
+        }
+    })
+}
+
+// Initializers for named fields read from `__values`, with flattened fields
+// read from `whole`
+fn read_fields(fields: &[Field], whole: TokenStream, ty: &str) -> TokenStream {
+    let initializers = fields.iter().map(|field| {
+        let member = &field.member;
+        let name = &field.name;
+        let value = if field.options.skip {
+            quote!(::core::default::Default::default())
+        } else if field.options.id {
+            quote!(::links_notation::convert::from_id(#whole, #ty)?)
+        } else if field.options.flatten {
+            quote!(::links_notation::FromLino::from_lino(#whole)?)
+        } else {
+            quote!(::links_notation::convert::read_field(__values, #name)?)
+        };
+        quote!(#member: #value)
+    });
+    quote!({ #(#initializers),* })
+}
+
+pub fn expand_from_lino(input: DeriveInput) -> Result<TokenStream> {
+    check_container(&input)?;
+    let ident = &input.ident;
+    let ty = ident.to_string();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = fields(&data.fields, true)?;
+            match &data.fields {
+                Fields::Named(_) => {
+                    let initializers = read_fields(&fields, quote!(lino), &ty);
+                    quote! {
+                        let __values = ::links_notation::convert::fields(lino, #ty)?;
+                        ::core::result::Result::Ok(Self #initializers)
+                    }
+                }
+                Fields::Unnamed(_) if fields.len() == 1 => {
+                    quote!(::core::result::Result::Ok(Self(::links_notation::FromLino::from_lino(lino)?)))
+                }
+                Fields::Unnamed(_) => {
+                    let len = fields.len();
+                    let indexes = 0..len;
+                    quote! {
+                        let __values = ::links_notation::convert::elements(
+                            ::links_notation::convert::fields(lino, #ty)?,
+                            #len,
+                            #ty,
+                        )?;
+                        ::core::result::Result::Ok(Self(#(::links_notation::FromLino::from_lino(&__values[#indexes])?),*))
+                    }
+                }
+                Fields::Unit => quote! {
+                    <() as ::links_notation::FromLino>::from_lino(lino)?;
+                    ::core::result::Result::Ok(Self)
+                },
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let name = variant_name(variant)?;
+                    let variant_ident = &variant.ident;
+                    let path = format!("{}::{}", ty, variant_ident);
+                    let fields = fields(&variant.fields, false)?;
+                    Ok(match &variant.fields {
+                        Fields::Unit => quote! {
+                            #name => {
+                                ::links_notation::convert::elements(__values, 0, #path)?;
+                                ::core::result::Result::Ok(Self::#variant_ident)
+                            }
+                        },
+                        Fields::Unnamed(_) if fields.len() == 1 => quote! {
+                            #name => ::core::result::Result::Ok(Self::#variant_ident(
+                                ::links_notation::convert::newtype(__values)?
+                            ))
+                        },
+                        Fields::Unnamed(_) => {
+                            let len = fields.len();
+                            let indexes = 0..len;
+                            quote! {
+                                #name => {
+                                    let __values = ::links_notation::convert::elements(__values, #len, #path)?;
+                                    ::core::result::Result::Ok(Self::#variant_ident(
+                                        #(::links_notation::FromLino::from_lino(&__values[#indexes])?),*
+                                    ))
+                                }
+                            }
+                        }
+                        Fields::Named(_) => {
+                            let initializers = read_fields(&fields, quote!(&__whole), &path);
+                            let whole = fields.iter().any(|field| field.options.flatten).then(|| {
+                                quote! {
+                                    let __whole = ::links_notation::LiNo::Link {
+                                        id: ::core::option::Option::None,
+                                        values: __values.to_vec(),
+                                    };
+                                }
+                            });
+                            quote! {
+                                #name => {
+                                    #whole
+                                    ::core::result::Result::Ok(Self::#variant_ident #initializers)
+                                }
+                            }
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {
+                let (__name, __values) = ::links_notation::convert::variant(lino, #ty)?;
+                match __name {
+                    #(#arms,)*
+                    _ => ::core::result::Result::Err(::links_notation::convert::unknown_variant(__name, #ty)),
+                }
+            }
+        }
+        Data::Union(_) => return Err(Error::new(ident.span(), "unions cannot be read from links")),
+    };
+    let generics = with_bound(&input.generics, quote!(::links_notation::FromLino));
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::links_notation::FromLino for #ident #type_generics #where_clause {
+            fn from_lino(
+                lino: &::links_notation::LiNo<::std::string::String>,
+            ) -> ::core::result::Result<Self, ::links_notation::Error> {
+                #body
+            }
+        }
+    })
+}
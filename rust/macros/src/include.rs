@@ -0,0 +1,54 @@
+//! Implementation of `include_lino!`.
+
+use std::path::{Path, PathBuf};
+
+use links_notation::{parse_lino_to_links, parser, LiNo};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, LitStr, Result};
+
+fn generate(lino: &LiNo<String>) -> TokenStream {
+    match lino {
+        LiNo::Ref(name) => quote!(::links_notation::borrowed::BorrowedLiNo::Ref(#name)),
+        LiNo::Link { id, values } => {
+            let id = match id {
+                Some(id) => quote!(::core::option::Option::Some(#id)),
+                None => quote!(::core::option::Option::None),
+            };
+            let values = values.iter().map(generate);
+            quote!(::links_notation::borrowed::BorrowedLiNo::Link { id: #id, values: &[#(#values),*] })
+        }
+    }
+}
+
+// Parses the document, reporting the line and column of a syntax error
+fn parse(document: &str, path: &Path) -> std::result::Result<Vec<LiNo<String>>, String> {
+    if !document.trim().is_empty() {
+        let offset = match parser::parse_document(document) {
+            Ok(_) => None,
+            Err(nom::Err::Error(error)) | Err(nom::Err::Failure(error)) => Some(document.len() - error.input.len()),
+            Err(nom::Err::Incomplete(_)) => Some(document.len()),
+        };
+        if let Some(offset) = offset {
+            let (line, column) = parser::line_column(document, offset);
+            return Err(format!("{}:{}:{}: invalid Links Notation", path.display(), line, column));
+        }
+    }
+    parse_lino_to_links(document).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+/// Expands `include_lino!("path")`, with `path` relative to `directory`.
+pub fn expand(path: LitStr, directory: PathBuf) -> Result<TokenStream> {
+    let path_buf = directory.join(path.value());
+    let document = std::fs::read_to_string(&path_buf)
+        .map_err(|error| Error::new(path.span(), format!("couldn't read {}: {}", path_buf.display(), error)))?;
+    let links = parse(&document, &path_buf).map_err(|message| Error::new(path.span(), message))?;
+    let links = links.iter().map(generate);
+    let tracked = path_buf.to_string_lossy();
+    Ok(quote! {{
+        // Rebuilds the crate when the file changes
+        const _: &[u8] = ::core::include_bytes!(#tracked);
+        const LINKS: &[::links_notation::borrowed::BorrowedLiNo<'static>] = &[#(#links),*];
+        LINKS
+    }})
+}
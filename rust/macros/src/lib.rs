@@ -0,0 +1,136 @@
+//! Procedural macros for the `links-notation` crate.
+//!
+//! The generated code refers to `::links_notation`, so crates using these
+//! macros also depend on `links-notation`.
+
+use proc_macro::TokenStream;
+
+mod derive;
+mod include;
+mod lino;
+
+/// Builds a `LiNo<String>` from Links Notation written inline.
+///
+/// The input is read as one line of a document: `lino!(papa: loves mama)` and
+/// `lino!((papa: loves mama))` are both the link with id `papa`, and
+/// `lino!(papa)` is a reference. References are identifiers, numbers or
+/// string literals, and `{expr}` interpolates any value that converts
+/// `Into<LiNo<String>>`, such as a `&str`, a `String`, another `LiNo<String>`
+/// or a `Vec` of them. An interpolated id only needs to implement `Display`.
+///
+/// ```
+/// use links_notation::LiNo;
+/// use links_notation_macros::lino;
+///
+/// let who = "mama";
+/// let link = lino!(papa: loves {who} ("red car" 42));
+/// assert_eq!(link.format(false), "(papa: loves mama ('red car' 42))");
+/// ```
+///
+/// Anything else is a compile error:
+///
+/// ```compile_fail
+/// use links_notation_macros::lino;
+///
+/// let link = lino!(a: b: c);
+/// ```
+///
+/// ```compile_fail
+/// use links_notation_macros::lino;
+///
+/// let link = lino!(is-a);
+/// ```
+#[proc_macro]
+pub fn lino(input: TokenStream) -> TokenStream {
+    lino::expand(input.into()).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Parses a Links Notation file at compile time and expands to its links, a
+/// `&'static [BorrowedLiNo<'static>]` equal to what `parse_lino_to_links`
+/// returns for the file.
+///
+/// Like `include_str!`, the path is relative to the file containing the call.
+/// Missing files and syntax errors are compile errors pointing to the line and
+/// column of the problem, and the crate is rebuilt when the file changes. The
+/// links are constants, so they can be kept in a `static` and copied into
+/// `LiNo<String>` with `links_notation::borrowed::to_links`:
+///
+/// ```no_run
+/// use links_notation::borrowed::{to_links, BorrowedLiNo};
+/// use links_notation_macros::include_lino;
+///
+/// static FAMILY: &[BorrowedLiNo] = include_lino!("../tests/data/family.lino");
+///
+/// let links = to_links(FAMILY);
+/// ```
+///
+/// ```compile_fail
+/// use links_notation_macros::include_lino;
+///
+/// let links = include_lino!("missing.lino");
+/// ```
+#[proc_macro]
+pub fn include_lino(input: TokenStream) -> TokenStream {
+    let path = syn::parse_macro_input!(input as syn::LitStr);
+    let directory = proc_macro::Span::call_site()
+        .local_file()
+        .and_then(|file| std::path::absolute(file).ok())
+        .and_then(|file| file.parent().map(std::path::Path::to_path_buf))
+        .unwrap_or_default();
+    include::expand(path, directory).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derives `ToLino`, writing a value the way `links_notation::to_lino` does:
+/// structs are links of `(field: value)` links, tuple structs are links of
+/// their values, newtypes are their value, and enum variants are `Unit`,
+/// `(Newtype: value)`, `(Tuple: a b)` and `(Struct: (field: value))`.
+///
+/// Fields and variants accept `#[lino(...)]` attributes:
+///
+/// - `rename = "name"` writes the field or variant under another name;
+/// - `id` writes the field as the id of the struct's link;
+/// - `flatten` writes the fields of a nested struct as fields of this one;
+/// - `skip` leaves the field out, and `FromLino` fills it with `Default`.
+///
+/// ```
+/// use links_notation::{FromLino, ToLino};
+/// use links_notation_macros::{FromLino, ToLino};
+///
+/// #[derive(Debug, PartialEq, ToLino, FromLino)]
+/// struct Person {
+///     #[lino(id)]
+///     name: String,
+///     #[lino(rename = "likes")]
+///     hobbies: Vec<String>,
+/// }
+///
+/// let person = Person { name: "son".into(), hobbies: vec!["ball".into(), "tea".into()] };
+/// let link = person.to_lino();
+/// assert_eq!(link.format(false), "(son: (likes: (ball tea)))");
+/// assert_eq!(Person::from_lino(&link).unwrap(), person);
+/// ```
+#[proc_macro_derive(ToLino, attributes(lino))]
+pub fn derive_to_lino(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    derive::expand_to_lino(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derives `FromLino`, reading the representation written by `ToLino`.
+///
+/// Missing fields are read from the empty link `()`, so a missing `Option`
+/// is `None` and a missing `Vec` is empty; fields of other types are
+/// required. Unknown fields are ignored.
+///
+/// Attributes that cannot apply are compile errors:
+///
+/// ```compile_fail
+/// use links_notation_macros::FromLino;
+///
+/// #[derive(FromLino)]
+/// struct Pair(#[lino(id)] String, String);
+/// ```
+#[proc_macro_derive(FromLino, attributes(lino))]
+pub fn derive_from_lino(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    derive::expand_from_lino(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
@@ -0,0 +1,121 @@
+//! Implementation of `lino!`.
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::{Error, Lit, Result};
+
+enum Id {
+    Name(String),
+    Expr(TokenStream),
+}
+
+enum Node {
+    Ref(String),
+    Expr(TokenStream),
+    Link { id: Option<Id>, values: Vec<Node> },
+}
+
+fn reference(literal: &proc_macro2::Literal) -> Result<String> {
+    let value = match Lit::new(literal.clone()) {
+        Lit::Str(string) => string.value(),
+        Lit::Char(char) => char.value().to_string(),
+        Lit::Int(_) | Lit::Float(_) => literal.to_string(),
+        _ => return Err(Error::new(literal.span(), "expected a string, character or number")),
+    };
+    if value.is_empty() {
+        return Err(Error::new(literal.span(), "empty references cannot be written in Links Notation"));
+    }
+    Ok(value)
+}
+
+// Parses the contents of a link: an optional id followed by `:`, then values
+fn contents(tokens: TokenStream) -> Result<(Option<Id>, Vec<Node>)> {
+    let mut id = None;
+    let mut values = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let node = match token {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                Node::Ref(name.strip_prefix("r#").map(str::to_string).unwrap_or(name))
+            }
+            TokenTree::Literal(literal) => Node::Ref(reference(&literal)?),
+            TokenTree::Group(group) => match group.delimiter() {
+                Delimiter::Parenthesis => link(group.stream())?,
+                Delimiter::Brace | Delimiter::None if group.stream().is_empty() => {
+                    return Err(Error::new(group.span(), "expected an expression"));
+                }
+                Delimiter::Brace | Delimiter::None => Node::Expr(group.stream()),
+                Delimiter::Bracket => return Err(Error::new(group.span(), "unexpected `[`")),
+            },
+            TokenTree::Punct(punct) if punct.as_char() == ':' => {
+                let single = punct.spacing() == proc_macro2::Spacing::Alone;
+                id = match (single, id.is_none(), values.pop()) {
+                    (true, true, Some(Node::Ref(name))) if values.is_empty() => Some(Id::Name(name)),
+                    (true, true, Some(Node::Expr(expr))) if values.is_empty() => Some(Id::Expr(expr)),
+                    _ => return Err(Error::new(punct.span(), "`:` can only follow the id at the start of a link")),
+                };
+                continue;
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '-' => match tokens.next() {
+                Some(TokenTree::Literal(literal)) if matches!(Lit::new(literal.clone()), Lit::Int(_) | Lit::Float(_)) => {
+                    Node::Ref(format!("-{}", literal))
+                }
+                _ => return Err(unexpected(punct.as_char(), punct.span())),
+            },
+            TokenTree::Punct(punct) => return Err(unexpected(punct.as_char(), punct.span())),
+        };
+        values.push(node);
+    }
+    Ok((id, values))
+}
+
+fn unexpected(c: char, span: Span) -> Error {
+    Error::new(
+        span,
+        format!(
+            "unexpected `{}`; write references that are not identifiers or numbers as string literals",
+            c
+        ),
+    )
+}
+
+// A parenthesized link; like the parser, `(a)` is the reference `a`
+fn link(tokens: TokenStream) -> Result<Node> {
+    let (id, mut values) = contents(tokens)?;
+    if id.is_none() && values.len() == 1 && !matches!(values[0], Node::Link { .. }) {
+        return Ok(values.remove(0));
+    }
+    Ok(Node::Link { id, values })
+}
+
+fn generate(node: &Node) -> TokenStream {
+    match node {
+        Node::Ref(name) => quote!(::links_notation::LiNo::Ref(::std::string::String::from(#name))),
+        Node::Expr(expr) => quote!(::core::convert::Into::<::links_notation::LiNo<::std::string::String>>::into(#expr)),
+        Node::Link { id, values } => {
+            let id = match id {
+                None => quote!(::core::option::Option::None),
+                Some(Id::Name(name)) => quote!(::core::option::Option::Some(::std::string::String::from(#name))),
+                Some(Id::Expr(expr)) => quote!(::core::option::Option::Some(::std::string::ToString::to_string(&(#expr)))),
+            };
+            let values = values.iter().map(generate);
+            quote!(::links_notation::LiNo::Link { id: #id, values: ::std::vec![#(#values),*] })
+        }
+    }
+}
+
+/// Expands the contents of a `lino!` call, read as a single line of Links
+/// Notation.
+pub fn expand(input: TokenStream) -> Result<TokenStream> {
+    if input.is_empty() {
+        return Err(Error::new(Span::call_site(), "expected a link or reference"));
+    }
+    let (id, mut values) = contents(input)?;
+    let node = if id.is_none() && values.len() == 1 {
+        values.remove(0)
+    } else {
+        Node::Link { id, values }
+    };
+    Ok(generate(&node))
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use links_notation::{parse_lino_to_links, FromLino, LiNo, ToLino};
+use links_notation_macros::{FromLino, ToLino};
+
+fn parsed(document: &str) -> LiNo<String> {
+    parse_lino_to_links(document).unwrap().remove(0)
+}
+
+#[derive(Debug, Default, PartialEq, ToLino, FromLino)]
+struct Address {
+    city: String,
+    zip: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, ToLino, FromLino)]
+struct Person {
+    #[lino(id)]
+    name: String,
+    age: u8,
+    #[lino(rename = "likes")]
+    hobbies: Vec<String>,
+    #[lino(flatten)]
+    address: Address,
+    #[lino(skip)]
+    cache: Option<String>,
+}
+
+#[derive(Debug, PartialEq, ToLino, FromLino)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect(u32, u32),
+    #[lino(rename = "poly")]
+    Polygon {
+        points: Vec<Point>,
+        closed: bool,
+    },
+}
+
+#[derive(Debug, PartialEq, ToLino, FromLino)]
+struct Point(i32, i32);
+
+#[derive(Debug, PartialEq, ToLino, FromLino)]
+struct Meters(f64);
+
+#[derive(Debug, PartialEq, ToLino, FromLino)]
+struct Marker;
+
+#[derive(Debug, PartialEq, ToLino, FromLino)]
+struct Tagged<T> {
+    r#type: T,
+    tags: BTreeMap<String, T>,
+}
+
+fn person() -> Person {
+    Person {
+        name: "son".to_string(),
+        age: 7,
+        hobbies: vec!["ball".to_string(), "tea".to_string()],
+        address: Address {
+            city: "Paris".to_string(),
+            zip: None,
+        },
+        cache: Some("ignored".to_string()),
+    }
+}
+
+#[test]
+fn test_struct_attributes() {
+    let link = person().to_lino();
+    assert_eq!(link, parsed("(son: (age: 7) (likes: (ball tea)) (city: Paris) (zip: ()))"));
+    let read = Person::from_lino(&link).unwrap();
+    assert_eq!(read, Person { cache: None, ..person() });
+}
+
+#[test]
+fn test_reads_written_documents() {
+    let person = Person::from_lino(&parsed("son: (likes: ball tea) (city: Paris) (age: 7)")).unwrap();
+    assert_eq!(person.hobbies, vec!["ball", "tea"]);
+    assert_eq!(person.address.zip, None);
+
+    let single = Person::from_lino(&parsed("son: (likes: ball) (city: Paris) (age: 7) (zip: 75001)")).unwrap();
+    assert_eq!(single.hobbies, vec!["ball"]);
+    assert_eq!(single.address.zip, Some(75001));
+}
+
+#[test]
+fn test_enums() {
+    let cases = [
+        (Shape::Empty, "Empty"),
+        (Shape::Circle(1.5), "(Circle: 1.5)"),
+        (Shape::Rect(2, 3), "(Rect: 2 3)"),
+        (
+            Shape::Polygon {
+                points: vec![Point(0, 0), Point(1, -1)],
+                closed: true,
+            },
+            "(poly: (points: ((0 0) (1 -1))) (closed: true))",
+        ),
+    ];
+    for (shape, text) in cases {
+        assert_eq!(shape.to_lino(), parsed(text));
+        assert_eq!(Shape::from_lino(&parsed(text)).unwrap(), shape);
+    }
+}
+
+#[test]
+fn test_tuple_newtype_and_unit_structs() {
+    assert_eq!(Meters(2.5).to_lino(), LiNo::Ref("2.5".to_string()));
+    assert_eq!(Meters::from_lino(&parsed("2.5")).unwrap(), Meters(2.5));
+    assert_eq!(Marker.to_lino(), parsed("()"));
+    assert_eq!(Marker::from_lino(&parsed("()")).unwrap(), Marker);
+    assert_eq!(Point::from_lino(&parsed("(3 4)")).unwrap(), Point(3, 4));
+}
+
+#[test]
+fn test_generics_and_raw_identifiers() {
+    let value = Tagged {
+        r#type: 1,
+        tags: BTreeMap::from([("a".to_string(), 2)]),
+    };
+    let link = value.to_lino();
+    assert_eq!(link, parsed("((type: 1) (tags: ((a: 2))))"));
+    assert_eq!(Tagged::<i64>::from_lino(&link).unwrap(), value);
+}
+
+#[test]
+fn test_errors() {
+    let missing = Person::from_lino(&parsed("son: (city: Paris)")).unwrap_err();
+    assert_eq!(missing.message(), "missing field `age`");
+    let invalid = Person::from_lino(&parsed("son: (age: old) (city: Paris)")).unwrap_err();
+    assert_eq!(invalid.message(), "field `age`: expected an integer, found `old`");
+    let unnamed = Person::from_lino(&parsed("(age: 7) (city: Paris)")).unwrap_err();
+    assert_eq!(unnamed.message(), "expected a link with an id for `Person`");
+    let variant = Shape::from_lino(&parsed("Triangle")).unwrap_err();
+    assert_eq!(variant.message(), "unknown variant `Triangle` of `Shape`");
+    let arity = Shape::from_lino(&parsed("Rect: 1")).unwrap_err();
+    assert_eq!(arity.message(), "expected 2 values for `Shape::Rect`, found 1");
+}
@@ -0,0 +1,53 @@
+use links_notation::borrowed::{to_links, BorrowedLiNo};
+use links_notation::{parse_lino_to_links, LiNo};
+use links_notation_macros::{include_lino, lino};
+
+fn parsed(document: &str) -> LiNo<String> {
+    parse_lino_to_links(document).unwrap().remove(0)
+}
+
+#[test]
+fn test_matches_the_parser() {
+    assert_eq!(lino!((papa: loves mama)), parsed("(papa: loves mama)"));
+    assert_eq!(lino!(papa: loves mama), parsed("papa: loves mama"));
+    assert_eq!(lino!(papa), parsed("papa"));
+    assert_eq!(lino!((papa)), parsed("(papa)"));
+    assert_eq!(lino!(a b), parsed("a b"));
+    assert_eq!(lino!(((a b))), parsed("((a b))"));
+    assert_eq!(lino!(()), parsed("()"));
+    assert_eq!(lino!(x: () (y: (z))), parsed("x: () (y: (z))"));
+    assert_eq!(lino!(son: (likes ball) (likes (red car))), parsed("son: (likes ball) (likes (red car))"));
+}
+
+#[test]
+fn test_literals() {
+    assert_eq!(lino!("red car": 1.5 -2 'c' r#type), parsed("('red car': 1.5 -2 c type)"));
+    assert_eq!(lino!("it's"), LiNo::Ref("it's".to_string()));
+}
+
+#[test]
+fn test_interpolation() {
+    let who = "mama";
+    let nested = lino!(likes ball);
+    let list = vec![LiNo::from("a"), LiNo::from("b")];
+    let id = 7;
+    assert_eq!(
+        lino!({id}: loves {who} {nested} {list} {String::from("tea")}),
+        parsed("(7: loves mama (likes ball) (a b) tea)")
+    );
+    assert_eq!(lino!({who}), LiNo::from("mama"));
+    assert_eq!(lino!(({who})), LiNo::from("mama"));
+}
+
+static FAMILY: &[BorrowedLiNo] = include_lino!("data/family.lino");
+
+#[test]
+fn test_include_lino() {
+    let expected = parse_lino_to_links(include_str!("data/family.lino")).unwrap();
+    assert_eq!(to_links(FAMILY), expected);
+    assert_eq!(LiNo::from(FAMILY[0]), lino!(papa: loves mama));
+    assert_eq!(FAMILY[0].id(), Some("papa"));
+
+    const LINKS: &[BorrowedLiNo] = include_lino!("data/family.lino");
+    assert_eq!(LINKS, FAMILY);
+}
@@ -0,0 +1,186 @@
+//! Cycle detection, strongly connected components, topological order and
+//! reachability over a [`LinkGraph`].
+//!
+//! A link has an edge to every link its values point to: links nested in it
+//! and links named by its references. Results are deterministic and list
+//! links by index; links with lower indices come first when there is a choice.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::graph::LinkGraph;
+
+impl LinkGraph {
+    fn successors_within(&self, included: &[bool]) -> Vec<Vec<usize>> {
+        (0..self.len())
+            .map(|index| {
+                if included[index] {
+                    self.outgoing(index).into_iter().filter(|target| included[*target]).collect()
+                } else {
+                    vec![]
+                }
+            })
+            .collect()
+    }
+
+    /// Strongly connected components, each sorted by index, ordered by their
+    /// first link.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        self.components_within(&vec![true; self.len()])
+    }
+
+    fn components_within(&self, included: &[bool]) -> Vec<Vec<usize>> {
+        let count = self.len();
+        let successors = self.successors_within(included);
+        let mut order = vec![usize::MAX; count];
+        let mut lowlink = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut counter = 0;
+
+        // Tarjan's algorithm with an explicit call stack of (link, next successor)
+        for start in 0..count {
+            if !included[start] || order[start] != usize::MAX {
+                continue;
+            }
+            let mut calls = vec![(start, 0)];
+            order[start] = counter;
+            lowlink[start] = counter;
+            counter += 1;
+            stack.push(start);
+            on_stack[start] = true;
+
+            while let Some((link, next)) = calls.last_mut() {
+                let link = *link;
+                if let Some(&successor) = successors[link].get(*next) {
+                    *next += 1;
+                    if order[successor] == usize::MAX {
+                        order[successor] = counter;
+                        lowlink[successor] = counter;
+                        counter += 1;
+                        stack.push(successor);
+                        on_stack[successor] = true;
+                        calls.push((successor, 0));
+                    } else if on_stack[successor] {
+                        lowlink[link] = lowlink[link].min(order[successor]);
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some(&(caller, _)) = calls.last() {
+                    lowlink[caller] = lowlink[caller].min(lowlink[link]);
+                }
+                if lowlink[link] == order[link] {
+                    let mut component = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == link {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+            }
+        }
+        components.sort_unstable_by_key(|component| component[0]);
+        components
+    }
+
+    /// Groups of links that point to each other, directly or through other
+    /// links, including links that point to themselves.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        self.cycles_within(&vec![true; self.len()])
+    }
+
+    fn cycles_within(&self, included: &[bool]) -> Vec<Vec<usize>> {
+        let successors = self.successors_within(included);
+        self.components_within(included)
+            .into_iter()
+            .filter(|component| component.len() > 1 || successors[component[0]].contains(&component[0]))
+            .collect()
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        !self.cycles().is_empty()
+    }
+
+    /// Orders all links so that every link comes before the links it points
+    /// to, or returns the cycles that make this impossible.
+    pub fn topological_order(&self) -> Result<Vec<usize>, Vec<Vec<usize>>> {
+        self.topological_order_of(&(0..self.len()).collect::<Vec<_>>())
+    }
+
+    /// Orders a subset of links so that every link comes before the links of
+    /// the subset it points to, or returns the cycles within the subset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index in `subset` is not less than [`LinkGraph::len`],
+    /// as do the other methods taking link indices.
+    pub fn topological_order_of(&self, subset: &[usize]) -> Result<Vec<usize>, Vec<Vec<usize>>> {
+        let mut included = vec![false; self.len()];
+        for &index in subset {
+            included[index] = true;
+        }
+        let successors = self.successors_within(&included);
+
+        let mut incoming = vec![0; self.len()];
+        for targets in &successors {
+            for &target in targets {
+                incoming[target] += 1;
+            }
+        }
+        let mut ready: BinaryHeap<_> = (0..self.len())
+            .filter(|index| included[*index] && incoming[*index] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = vec![];
+        while let Some(Reverse(index)) = ready.pop() {
+            order.push(index);
+            for &target in &successors[index] {
+                incoming[target] -= 1;
+                if incoming[target] == 0 {
+                    ready.push(Reverse(target));
+                }
+            }
+        }
+
+        if order.len() == included.iter().filter(|included| **included).count() {
+            Ok(order)
+        } else {
+            Err(self.cycles_within(&included))
+        }
+    }
+
+    /// Links reachable from a link by following its values, in breadth-first
+    /// order. The link itself is only included when it lies on a cycle.
+    pub fn reachable(&self, index: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.len()];
+        let mut queue = VecDeque::from([index]);
+        let mut reachable = vec![];
+        while let Some(link) = queue.pop_front() {
+            for target in self.outgoing(link) {
+                if !visited[target] {
+                    visited[target] = true;
+                    reachable.push(target);
+                    queue.push_back(target);
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Links reachable from the link defining `id`, or `None` if no link
+    /// defines it.
+    pub fn reachable_from(&self, id: &str) -> Option<Vec<usize>> {
+        self.definition_of(id).map(|index| self.reachable(index))
+    }
+
+    /// Whether `to` can be reached from `from` by following values.
+    pub fn is_reachable(&self, from: usize, to: usize) -> bool {
+        self.reachable(from).contains(&to)
+    }
+}
@@ -0,0 +1,342 @@
+//! Compact binary encoding of links.
+//!
+//! All integers are little-endian `u32`s. A file is laid out as:
+//!
+//! | Part         | Contents                                                    |
+//! |--------------|-------------------------------------------------------------|
+//! | header       | magic `LINO`, version (`u16`), flags (`u16`, zero), then the number of strings, links, values and roots |
+//! | string table | for each string, its length in bytes and its UTF-8 bytes    |
+//! | links        | fixed-width records: id (string index + 1, or 0 for none), index of the first value, number of values |
+//! | values       | one entry per value of every link                           |
+//! | roots        | one entry per top-level link                                |
+//! | checksum     | CRC-32 (IEEE) of everything before it                       |
+//!
+//! A value or root entry with the high bit set is a reference, naming the
+//! string with the remaining bits as index; otherwise it is the index of a
+//! link record. Records only point to earlier records, so links are rebuilt
+//! in a single pass. Every string is stored once and identical nested links
+//! share a record.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use crate::error::{Error, Result};
+use crate::store::LinkStore;
+use crate::LiNo;
+
+const MAGIC: &[u8; 4] = b"LINO";
+
+/// Version of the format written by this crate.
+pub const VERSION: u16 = 1;
+
+const REFERENCE: u32 = 1 << 31;
+const HEADER_LEN: usize = 4 + 2 + 2 + 4 * 4;
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+fn count(len: usize, what: &str) -> Result<u32> {
+    u32::try_from(len)
+        .ok()
+        .filter(|len| len & REFERENCE == 0)
+        .ok_or_else(|| Error::new(format!("too many {} to encode", what)))
+}
+
+#[derive(Default)]
+struct Encoder {
+    strings: Vec<String>,
+    string_indices: HashMap<String, u32>,
+    links: Vec<(u32, Vec<u32>)>,
+    link_indices: HashMap<(u32, Vec<u32>), u32>,
+}
+
+impl Encoder {
+    fn string(&mut self, value: &str) -> Result<u32> {
+        if let Some(index) = self.string_indices.get(value) {
+            return Ok(*index);
+        }
+        let index = count(self.strings.len(), "strings")?;
+        self.strings.push(value.to_string());
+        self.string_indices.insert(value.to_string(), index);
+        Ok(index)
+    }
+
+    fn value(&mut self, lino: &LiNo<String>) -> Result<u32> {
+        match lino {
+            LiNo::Ref(name) => Ok(self.string(name)? | REFERENCE),
+            LiNo::Link { id, values } => {
+                let id = match id {
+                    Some(id) => self.string(id)? + 1,
+                    None => 0,
+                };
+                let values = values.iter().map(|value| self.value(value)).collect::<Result<Vec<_>>>()?;
+                let key = (id, values);
+                if let Some(index) = self.link_indices.get(&key) {
+                    return Ok(*index);
+                }
+                let index = count(self.links.len(), "links")?;
+                self.links.push(key.clone());
+                self.link_indices.insert(key, index);
+                Ok(index)
+            }
+        }
+    }
+}
+
+fn push(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Encodes links into bytes.
+pub fn to_bytes(links: &[LiNo<String>]) -> Result<Vec<u8>> {
+    let mut encoder = Encoder::default();
+    let roots = links.iter().map(|link| encoder.value(link)).collect::<Result<Vec<_>>>()?;
+    let value_count = count(encoder.links.iter().map(|(_, values)| values.len()).sum(), "values")?;
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    push(&mut bytes, encoder.strings.len() as u32);
+    push(&mut bytes, encoder.links.len() as u32);
+    push(&mut bytes, value_count);
+    push(&mut bytes, count(roots.len(), "roots")?);
+    for string in &encoder.strings {
+        push(&mut bytes, count(string.len(), "bytes in a string")?);
+        bytes.extend_from_slice(string.as_bytes());
+    }
+    let mut first = 0;
+    for (id, values) in &encoder.links {
+        push(&mut bytes, *id);
+        push(&mut bytes, first);
+        push(&mut bytes, values.len() as u32);
+        first += values.len() as u32;
+    }
+    for value in encoder.links.iter().flat_map(|(_, values)| values) {
+        push(&mut bytes, *value);
+    }
+    for root in roots {
+        push(&mut bytes, root);
+    }
+    let checksum = crc32(&bytes);
+    push(&mut bytes, checksum);
+    Ok(bytes)
+}
+
+/// Writes encoded links to `writer`.
+pub fn write_links<W: Write>(mut writer: W, links: &[LiNo<String>]) -> Result<()> {
+    writer.write_all(&to_bytes(links)?).map_err(|e| Error::new(e.to_string()))
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.position.checked_add(len).filter(|end| *end <= self.bytes.len());
+        let end = end.ok_or_else(|| Error::new("unexpected end of data"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+}
+
+/// Most links and references [`from_bytes`] builds. Identical links share a
+/// record, so a small file can describe a huge tree; decoding stops with an
+/// error past this size. [`LinkStore::from_bytes`] keeps records shared and
+/// has no such limit.
+pub const MAX_NODES: usize = 1 << 24;
+
+/// Deepest nesting of links [`from_bytes`] builds. Links are built and
+/// dropped recursively, so deeper files are rejected rather than overflowing
+/// the stack.
+pub const MAX_DEPTH: usize = 1 << 10;
+
+// Contents of a file, with records and roots as stored
+struct Decoded {
+    strings: Vec<String>,
+    records: Vec<(Option<usize>, Vec<u32>)>,
+    roots: Vec<u32>,
+}
+
+fn check_value(value: u32, strings: usize, links: usize) -> Result<()> {
+    let index = (value & !REFERENCE) as usize;
+    let limit = if value & REFERENCE != 0 { strings } else { links };
+    if index < limit {
+        Ok(())
+    } else {
+        Err(Error::new(format!("invalid value {:#x}", value)))
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<Decoded> {
+    if bytes.len() < HEADER_LEN + 4 || &bytes[..4] != MAGIC {
+        return Err(Error::new("not a binary links file"));
+    }
+    let (body, checksum) = bytes.split_at(bytes.len() - 4);
+    if crc32(body) != u32::from_le_bytes(checksum.try_into().unwrap()) {
+        return Err(Error::new("checksum mismatch"));
+    }
+    let mut decoder = Decoder { bytes: body, position: 4 };
+    let version = decoder.u16()?;
+    if version != VERSION {
+        return Err(Error::new(format!("unsupported version {}", version)));
+    }
+    let flags = decoder.u16()?;
+    if flags != 0 {
+        return Err(Error::new(format!("unsupported flags {:#x}", flags)));
+    }
+    let string_count = decoder.u32()? as usize;
+    let link_count = decoder.u32()? as usize;
+    let value_count = decoder.u32()? as usize;
+    let root_count = decoder.u32()? as usize;
+
+    // Counts come from the file, so capacities are bounded by its size
+    let mut strings = Vec::with_capacity(string_count.min(body.len() / 4));
+    for _ in 0..string_count {
+        let len = decoder.u32()? as usize;
+        let string = std::str::from_utf8(decoder.take(len)?).map_err(|e| Error::new(e.to_string()))?;
+        strings.push(string.to_string());
+    }
+    let records = decoder.take(link_count.checked_mul(12).ok_or_else(|| Error::new("too many links"))?)?;
+    let values = decoder.take(value_count.checked_mul(4).ok_or_else(|| Error::new("too many values"))?)?;
+    let value = |index: usize| u32::from_le_bytes(values[index * 4..index * 4 + 4].try_into().unwrap());
+
+    let mut decoded = Decoded {
+        records: Vec::with_capacity(link_count),
+        roots: Vec::with_capacity(root_count.min(body.len() / 4)),
+        strings,
+    };
+    for record in records.chunks_exact(12) {
+        let field = |index: usize| u32::from_le_bytes(record[index * 4..index * 4 + 4].try_into().unwrap());
+        let id = match field(0) as usize {
+            0 => None,
+            id if id <= decoded.strings.len() => Some(id - 1),
+            id => return Err(Error::new(format!("invalid id {}", id))),
+        };
+        let (first, len) = (field(1) as usize, field(2) as usize);
+        if first.checked_add(len).is_none_or(|end| end > value_count) {
+            return Err(Error::new(format!("values of link {} are out of range", decoded.records.len())));
+        }
+        let link_values: Vec<u32> = (first..first + len).map(value).collect();
+        for value in &link_values {
+            // Links may only point to earlier records
+            check_value(*value, decoded.strings.len(), decoded.records.len())?;
+        }
+        decoded.records.push((id, link_values));
+    }
+    for _ in 0..root_count {
+        let root = decoder.u32()?;
+        check_value(root, decoded.strings.len(), decoded.records.len())?;
+        decoded.roots.push(root);
+    }
+    if decoder.position != body.len() {
+        return Err(Error::new("unexpected data after roots"));
+    }
+    Ok(decoded)
+}
+
+/// Decodes links from bytes, verifying the version and checksum.
+pub fn from_bytes(bytes: &[u8]) -> Result<Vec<LiNo<String>>> {
+    let Decoded { strings, records, roots } = decode(bytes)?;
+    // Number of links and references each record expands to, and how deeply
+    // its links nest
+    let mut sizes: Vec<usize> = Vec::with_capacity(records.len());
+    let mut depths: Vec<usize> = Vec::with_capacity(records.len());
+    let size = |value: u32, sizes: &[usize]| if value & REFERENCE != 0 { 1 } else { sizes[value as usize] };
+    let depth = |value: u32, depths: &[usize]| if value & REFERENCE != 0 { 0 } else { depths[value as usize] };
+    for (_, values) in &records {
+        let total = values.iter().fold(1usize, |total, value| total.saturating_add(size(*value, &sizes)));
+        sizes.push(total.min(MAX_NODES + 1));
+        let deepest = values.iter().map(|value| depth(*value, &depths)).max().unwrap_or(0);
+        depths.push(deepest + 1);
+    }
+    let total = roots.iter().fold(0usize, |total, root| total.saturating_add(size(*root, &sizes)));
+    if total > MAX_NODES {
+        return Err(Error::new(format!("links expand to more than {} nodes", MAX_NODES)));
+    }
+    if roots.iter().any(|root| depth(*root, &depths) > MAX_DEPTH) {
+        return Err(Error::new(format!("links nest more than {} deep", MAX_DEPTH)));
+    }
+
+    fn build(value: u32, strings: &[String], records: &[(Option<usize>, Vec<u32>)]) -> LiNo<String> {
+        let index = (value & !REFERENCE) as usize;
+        if value & REFERENCE != 0 {
+            return LiNo::Ref(strings[index].clone());
+        }
+        let (id, values) = &records[index];
+        LiNo::Link {
+            id: id.map(|id| strings[id].clone()),
+            values: values.iter().map(|value| build(*value, strings, records)).collect(),
+        }
+    }
+    Ok(roots.iter().map(|root| build(*root, &strings, &records)).collect())
+}
+
+/// Reads encoded links from `reader`.
+pub fn read_links<R: Read>(mut reader: R) -> Result<Vec<LiNo<String>>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(|e| Error::new(e.to_string()))?;
+    from_bytes(&bytes)
+}
+
+impl LinkStore {
+    /// Encodes the links of the store, as exported by [`LinkStore::to_lino`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        to_bytes(&self.to_lino())
+    }
+
+    /// Builds a store from encoded links. Records are stored as they are
+    /// read, so links shared in the file stay shared in the store.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let Decoded { strings, records, roots } = decode(bytes)?;
+        let mut store = LinkStore::new();
+        let mut addresses = Vec::with_capacity(records.len());
+        for (id, values) in records {
+            let values = values
+                .into_iter()
+                .map(|value| {
+                    let index = (value & !REFERENCE) as usize;
+                    if value & REFERENCE != 0 {
+                        store.point(&strings[index])
+                    } else {
+                        addresses[index]
+                    }
+                })
+                .collect();
+            let address = match id {
+                Some(id) => store.create_named(&strings[id], values)?,
+                None => store.create(values)?,
+            };
+            addresses.push(address);
+        }
+        for root in roots {
+            // Top-level references have no record
+            let address = if root & REFERENCE != 0 {
+                store.point(&strings[(root & !REFERENCE) as usize])
+            } else {
+                addresses[root as usize]
+            };
+            store.add_root(address);
+        }
+        Ok(store)
+    }
+}
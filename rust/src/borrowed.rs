@@ -0,0 +1,95 @@
+//! Links borrowing their names and values, for links known at compile time.
+//!
+//! A [`BorrowedLiNo`] holds `&str` names and a slice of values, so links can
+//! be written as constants and kept in a `static` without allocating.
+//! `include_lino!` from `links-notation-macros` expands to a
+//! `&'static [BorrowedLiNo<'static>]`. Converting to `LiNo<String>` copies
+//! the links.
+//!
+//! ```
+//! use links_notation::borrowed::BorrowedLiNo;
+//! use links_notation::LiNo;
+//!
+//! static LOVES: BorrowedLiNo = BorrowedLiNo::Link {
+//!     id: Some("papa"),
+//!     values: &[BorrowedLiNo::Ref("loves"), BorrowedLiNo::Ref("mama")],
+//! };
+//!
+//! assert_eq!(LiNo::from(&LOVES).format(false), "(papa: loves mama)");
+//! ```
+
+use std::fmt;
+
+use crate::convert::ToLino;
+use crate::LiNo;
+
+/// A link or reference that borrows its names and values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BorrowedLiNo<'a> {
+    Link {
+        id: Option<&'a str>,
+        values: &'a [BorrowedLiNo<'a>],
+    },
+    Ref(&'a str),
+}
+
+impl<'a> BorrowedLiNo<'a> {
+    pub const fn is_ref(&self) -> bool {
+        matches!(self, BorrowedLiNo::Ref(_))
+    }
+
+    pub const fn is_link(&self) -> bool {
+        matches!(self, BorrowedLiNo::Link { .. })
+    }
+
+    /// Id of a link; references have none.
+    pub const fn id(&self) -> Option<&'a str> {
+        match self {
+            BorrowedLiNo::Link { id, .. } => *id,
+            BorrowedLiNo::Ref(_) => None,
+        }
+    }
+
+    /// Values of a link; references have none.
+    pub const fn values(&self) -> &'a [BorrowedLiNo<'a>] {
+        match self {
+            BorrowedLiNo::Link { values, .. } => values,
+            BorrowedLiNo::Ref(_) => &[],
+        }
+    }
+}
+
+impl From<&BorrowedLiNo<'_>> for LiNo<String> {
+    fn from(lino: &BorrowedLiNo<'_>) -> Self {
+        match lino {
+            BorrowedLiNo::Link { id, values } => LiNo::Link {
+                id: id.map(str::to_string),
+                values: values.iter().map(LiNo::from).collect(),
+            },
+            BorrowedLiNo::Ref(name) => LiNo::Ref(name.to_string()),
+        }
+    }
+}
+
+impl From<BorrowedLiNo<'_>> for LiNo<String> {
+    fn from(lino: BorrowedLiNo<'_>) -> Self {
+        LiNo::from(&lino)
+    }
+}
+
+impl ToLino for BorrowedLiNo<'_> {
+    fn to_lino(&self) -> LiNo<String> {
+        LiNo::from(self)
+    }
+}
+
+impl fmt::Display for BorrowedLiNo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        LiNo::from(self).fmt(f)
+    }
+}
+
+/// Copies borrowed links, such as those of `include_lino!`.
+pub fn to_links(links: &[BorrowedLiNo<'_>]) -> Vec<LiNo<String>> {
+    links.iter().map(LiNo::from).collect()
+}
@@ -0,0 +1,379 @@
+//! Generation of Rust types from a [`Schema`].
+//!
+//! Every definition with an id becomes a type named after the id in
+//! UpperCamelCase, implementing [`ToLino`](crate::ToLino) and
+//! [`FromLino`](crate::FromLino) for the links the definition describes:
+//!
+//! - a definition with one value that is a `ref` or a `number` is a type alias
+//!   for `String` or `f64`, written as `(id: value)` when used as a field;
+//! - one with one value and `references` listing names is an enum of those
+//!   names;
+//! - one whose values are all ids is a struct with a field per id, optional
+//!   unless the id is in `requires`; fields of ids without a definition are
+//!   kept as `LiNo<::std::string::String>`;
+//! - any other definition is a struct holding its values.
+//!
+//! Fields whose struct contains the struct itself are boxed. Ids that would
+//! give two types, two fields of a struct or two variants of an enum the same
+//! Rust name are errors.
+//! The generated code names everything by its full path, so ids such as
+//! `string` or `option` do not clash with the prelude.
+//!
+//! From a build script, [`build`] writes the code to `OUT_DIR`, to be included
+//! with `include!(concat!(env!("OUT_DIR"), "/schema.rs"))`.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::schema::{Definition, Kind, Schema, Selector, Target};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "static",
+    "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+    "final", "gen", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+fn words(id: &str) -> Result<Vec<String>> {
+    let words: Vec<String> = id
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect();
+    match words.first().and_then(|word| word.chars().next()) {
+        Some(first) if !first.is_numeric() => Ok(words),
+        _ => Err(Error::new(format!("`{}` cannot be used as a Rust name", id))),
+    }
+}
+
+fn type_name(id: &str) -> Result<String> {
+    let name = words(id)?
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect();
+    if name == "Self" {
+        return Err(Error::new(format!("`{}` cannot be used as a Rust name", id)));
+    }
+    Ok(name)
+}
+
+fn field_name(id: &str) -> Result<String> {
+    let mut name = String::new();
+    for word in words(id)? {
+        if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+        // Splits camelCase words
+        for (index, c) in word.chars().enumerate() {
+            if c.is_uppercase() && index > 0 {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        }
+    }
+    if KEYWORDS.contains(&name.as_str()) {
+        name.insert_str(0, "r#");
+    }
+    Ok(name)
+}
+
+/// How a definition is represented in Rust.
+enum Shape<'a> {
+    Alias(&'static str),
+    Names(Vec<&'a str>),
+    Fields(Vec<&'a str>),
+    Values(&'static str),
+}
+
+fn shape(definition: &Definition) -> Shape<'_> {
+    let single = definition.arity == Some((1, Some(1)));
+    let kinds = definition.values.as_deref();
+    let names: Option<Vec<&str>> = definition.references.as_ref().and_then(|targets| {
+        targets
+            .iter()
+            .map(|target| match target {
+                Target::Name(name) => Some(name.as_str()),
+                Target::Defined => None,
+            })
+            .collect()
+    });
+    match (kinds, names) {
+        (None | Some([Kind::Ref]), Some(names)) if single => Shape::Names(names),
+        (Some([Kind::Ref]), _) if single => Shape::Alias("::std::string::String"),
+        (Some([Kind::Number]), _) if single => Shape::Alias("::core::primitive::f64"),
+        (Some(kinds), _) if !kinds.is_empty() && kinds.iter().all(|kind| matches!(kind, Kind::Id(_))) => {
+            Shape::Fields(
+                kinds
+                    .iter()
+                    .filter_map(|kind| match kind {
+                        Kind::Id(id) => Some(id.as_str()),
+                        _ => None,
+                    })
+                    .collect(),
+            )
+        }
+        (Some(kinds), _) if !kinds.is_empty() && kinds.iter().all(|kind| kind == &Kind::Ref) => Shape::Values("::std::string::String"),
+        (Some(kinds), _) if !kinds.is_empty() && kinds.iter().all(|kind| kind == &Kind::Number) => Shape::Values("::core::primitive::f64"),
+        _ => Shape::Values("::links_notation::LiNo<::std::string::String>"),
+    }
+}
+
+struct Generator<'a> {
+    schema: &'a Schema,
+    code: String,
+}
+
+impl Generator<'_> {
+    // Whether values of `id` are written as `(id: value)` fields rather than
+    // as links of their own
+    fn is_scalar(&self, id: &str) -> bool {
+        self.definition(id)
+            .is_some_and(|definition| matches!(shape(definition), Shape::Alias(_) | Shape::Names(_)))
+    }
+
+    fn definition(&self, id: &str) -> Option<&Definition> {
+        self.schema
+            .definitions()
+            .iter()
+            .find(|definition| definition.selector == Selector::Id(id.to_string()))
+    }
+
+    // Whether a struct for `from` contains `target`, directly or through the
+    // fields of other structs
+    fn contains(&self, from: &str, target: &str) -> bool {
+        let mut stack = vec![from];
+        let mut seen = vec![];
+        while let Some(id) = stack.pop() {
+            if id == target {
+                return true;
+            }
+            if seen.contains(&id) {
+                continue;
+            }
+            seen.push(id);
+            if let Some(Shape::Fields(ids)) = self.definition(id).map(shape) {
+                stack.extend(ids);
+            }
+        }
+        false
+    }
+
+    fn field_type(&self, id: &str) -> Result<String> {
+        match self.definition(id) {
+            Some(_) => type_name(id),
+            None => Ok("::links_notation::LiNo<::std::string::String>".to_string()),
+        }
+    }
+
+    fn line(&mut self, indent: usize, text: &str) {
+        let _ = writeln!(self.code, "{:indent$}{}", "", text, indent = indent * 4);
+    }
+
+    fn definition_code(&mut self, id: &str, definition: &Definition) -> Result<()> {
+        let name = type_name(id)?;
+        let derive = "#[derive(Debug, Clone, PartialEq)]";
+        match shape(definition) {
+            Shape::Alias(ty) => {
+                self.line(0, &format!("pub type {} = {};", name, ty));
+            }
+            Shape::Names(names) => {
+                let mut variants: Vec<String> = vec![];
+                for reference in &names {
+                    let variant = type_name(reference)?;
+                    if let Some(other) = variants.iter().position(|other| *other == variant) {
+                        return Err(Error::new(format!(
+                            "`{}` and `{}` in `{}` are both written as the variant `{}`",
+                            names[other], reference, id, variant
+                        )));
+                    }
+                    variants.push(variant);
+                }
+                self.line(0, derive);
+                self.line(0, &format!("pub enum {} {{", name));
+                for variant in &variants {
+                    self.line(1, &format!("{},", variant));
+                }
+                self.line(0, "}");
+                self.code.push('\n');
+                self.impl_header("ToLino", &name);
+                self.line(1, "fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {");
+                self.line(2, "let name = match self {");
+                for (variant, reference) in variants.iter().zip(&names) {
+                    self.line(3, &format!("{}::{} => {:?},", name, variant, reference));
+                }
+                self.line(2, "};");
+                self.line(2, "::links_notation::LiNo::Ref(name.to_string())");
+                self.line(1, "}");
+                self.line(0, "}");
+                self.code.push('\n');
+                self.impl_header("FromLino", &name);
+                self.read_header();
+                self.line(2, "match lino {");
+                for (variant, reference) in variants.iter().zip(&names) {
+                    self.line(
+                        3,
+                        &format!("::links_notation::LiNo::Ref(name) if name == {:?} => ::core::result::Result::Ok({}::{}),", reference, name, variant),
+                    );
+                }
+                self.line(
+                    3,
+                    &format!(
+                        "_ => ::core::result::Result::Err(::links_notation::Error::new(format!(\"expected a `{{}}`, found `{{}}`\", {:?}, lino))),",
+                        id
+                    ),
+                );
+                self.line(2, "}");
+                self.line(1, "}");
+                self.line(0, "}");
+            }
+            Shape::Fields(ids) => {
+                let name_id = id;
+                let mut fields: Vec<(&str, String, bool)> = vec![];
+                for field in ids {
+                    let field_name = field_name(field)?;
+                    if let Some((other, _, _)) = fields.iter().find(|(_, other, _)| *other == field_name) {
+                        return Err(Error::new(format!(
+                            "`{}` and `{}` in `{}` are both written as the field `{}`",
+                            other, field, id, field_name
+                        )));
+                    }
+                    fields.push((field, field_name, definition.requires.iter().any(|required| required == field)));
+                }
+                self.line(0, derive);
+                self.line(0, &format!("pub struct {} {{", name));
+                for (id, field, required) in &fields {
+                    let mut ty = self.field_type(id)?;
+                    // Recursive fields are boxed to keep the struct sized
+                    if self.contains(id, name_id) {
+                        ty = format!("::std::boxed::Box<{}>", ty);
+                    }
+                    let ty = if *required { ty } else { format!("::core::option::Option<{}>", ty) };
+                    self.line(1, &format!("pub {}: {},", field, ty));
+                }
+                self.line(0, "}");
+                self.code.push('\n');
+                self.impl_header("ToLino", &name);
+                self.line(1, "fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {");
+                self.line(2, "let mut values = ::std::vec::Vec::new();");
+                for (id, field, required) in &fields {
+                    let push = if self.is_scalar(id) {
+                        format!("values.push(::links_notation::convert::field({:?}, value));", id)
+                    } else {
+                        "values.push(::links_notation::ToLino::to_lino(value));".to_string()
+                    };
+                    if *required {
+                        self.line(2, &format!("let value = &self.{};", field));
+                        self.line(2, &push);
+                    } else {
+                        self.line(2, &format!("if let ::core::option::Option::Some(value) = &self.{} {{", field));
+                        self.line(3, &push);
+                        self.line(2, "}");
+                    }
+                }
+                self.line(2, "::links_notation::LiNo::Link {");
+                self.line(3, &format!("id: ::core::option::Option::Some({:?}.to_string()),", id));
+                self.line(3, "values,");
+                self.line(2, "}");
+                self.line(1, "}");
+                self.line(0, "}");
+                self.code.push('\n');
+                self.impl_header("FromLino", &name);
+                self.read_header();
+                self.line(2, &format!("let values = ::links_notation::convert::expect_id(lino, {:?})?;", id));
+                self.line(2, &format!("::core::result::Result::Ok({} {{", name));
+                for (id, field, required) in &fields {
+                    let read = match (self.is_scalar(id), required) {
+                        (true, _) => format!("::links_notation::convert::read_field(values, {:?})?", id),
+                        (false, true) => format!("::links_notation::convert::read_link(values, {:?})?", id),
+                        (false, false) => format!(
+                            "::links_notation::convert::find(values, {:?}).map(::links_notation::FromLino::from_lino).transpose()?",
+                            id
+                        ),
+                    };
+                    self.line(3, &format!("{}: {},", field, read));
+                }
+                self.line(2, "})");
+                self.line(1, "}");
+                self.line(0, "}");
+            }
+            Shape::Values(ty) => {
+                self.line(0, derive);
+                self.line(0, &format!("pub struct {} {{", name));
+                self.line(1, &format!("pub values: ::std::vec::Vec<{}>,", ty));
+                self.line(0, "}");
+                self.code.push('\n');
+                self.impl_header("ToLino", &name);
+                self.line(1, "fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {");
+                self.line(2, "::links_notation::LiNo::Link {");
+                self.line(3, &format!("id: ::core::option::Option::Some({:?}.to_string()),", id));
+                self.line(3, "values: self.values.iter().map(::links_notation::ToLino::to_lino).collect(),");
+                self.line(2, "}");
+                self.line(1, "}");
+                self.line(0, "}");
+                self.code.push('\n');
+                self.impl_header("FromLino", &name);
+                self.read_header();
+                self.line(2, &format!("let values = ::links_notation::convert::expect_id(lino, {:?})?;", id));
+                self.line(2, &format!("::core::result::Result::Ok({} {{", name));
+                self.line(
+                    3,
+                    "values: values.iter().map(::links_notation::FromLino::from_lino).collect::<::core::result::Result<_, _>>()?,",
+                );
+                self.line(2, "})");
+                self.line(1, "}");
+                self.line(0, "}");
+            }
+        }
+        Ok(())
+    }
+
+    fn impl_header(&mut self, name: &str, ty: &str) {
+        self.line(0, &format!("impl ::links_notation::{} for {} {{", name, ty));
+    }
+
+    fn read_header(&mut self) {
+        self.line(
+            1,
+            "fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {",
+        );
+    }
+}
+
+/// Generates Rust source code for the types described by a schema.
+pub fn generate(schema: &Schema) -> Result<String> {
+    let mut generator = Generator {
+        schema,
+        code: String::from("// Generated from a Links Notation schema; do not edit.\n"),
+    };
+    let mut names: Vec<(String, &str)> = vec![];
+    for definition in schema.definitions() {
+        if let Selector::Id(id) = &definition.selector {
+            let name = type_name(id)?;
+            if let Some((_, other)) = names.iter().find(|(other, _)| *other == name) {
+                return Err(Error::new(format!("`{}` and `{}` are both written as the type `{}`", other, id, name)));
+            }
+            names.push((name, id));
+            generator.code.push('\n');
+            generator.definition_code(id, definition)?;
+        }
+    }
+    Ok(generator.code)
+}
+
+/// Generates the types of the schema file at `schema` into `OUT_DIR/file_name`,
+/// for use from a build script, and asks Cargo to rerun it when the schema
+/// changes.
+pub fn build(schema: impl AsRef<Path>, file_name: &str) -> Result<()> {
+    let schema = schema.as_ref();
+    println!("cargo:rerun-if-changed={}", schema.display());
+    let document = std::fs::read_to_string(schema)
+        .map_err(|error| Error::new(format!("couldn't read {}: {}", schema.display(), error)))?;
+    let code = generate(&Schema::parse(&document)?)?;
+    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| Error::new("OUT_DIR is not set; call `build` from a build script"))?;
+    let path = Path::new(&out_dir).join(file_name);
+    std::fs::write(&path, code).map_err(|error| Error::new(format!("couldn't write {}: {}", path.display(), error)))
+}
@@ -0,0 +1,351 @@
+//! Conversion of Rust values to and from links without serde.
+//!
+//! [`ToLino`] and [`FromLino`] use the same representation as the serde
+//! support:
+//!
+//! - numbers, booleans, characters and strings are references;
+//! - `None`, `()` and empty strings are the empty link `()`;
+//! - sequences are links of their elements, and a single reference is read as
+//!   a sequence of one element;
+//! - structs are links of `(field: value)` links;
+//! - enum variants are `Unit`, `(Newtype: value)`, `(Tuple: a b)` and
+//!   `(Struct: (field: value))`.
+//!
+//! Both traits can be derived with the `links-notation-macros` crate.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::error::{Error, Result};
+use crate::LiNo;
+
+/// Conversion of a value into a link.
+pub trait ToLino {
+    fn to_lino(&self) -> LiNo<String>;
+}
+
+/// Conversion of a link into a value.
+pub trait FromLino: Sized {
+    fn from_lino(lino: &LiNo<String>) -> Result<Self>;
+}
+
+fn empty() -> LiNo<String> {
+    LiNo::Link { id: None, values: vec![] }
+}
+
+fn is_empty(lino: &LiNo<String>) -> bool {
+    matches!(lino, LiNo::Link { id: None, values } if values.is_empty())
+}
+
+macro_rules! impl_display {
+    ($($ty:ty => $expected:literal),* $(,)?) => {$(
+        impl ToLino for $ty {
+            fn to_lino(&self) -> LiNo<String> {
+                LiNo::Ref(self.to_string())
+            }
+        }
+
+        impl FromLino for $ty {
+            fn from_lino(lino: &LiNo<String>) -> Result<Self> {
+                match lino {
+                    LiNo::Ref(value) => value
+                        .parse()
+                        .map_err(|_| Error::new(format!("expected {}, found `{}`", $expected, value))),
+                    _ => Err(Error::new(format!("expected {}, found a link", $expected))),
+                }
+            }
+        }
+    )*};
+}
+
+impl_display! {
+    bool => "a boolean",
+    char => "a character",
+    i8 => "an integer",
+    i16 => "an integer",
+    i32 => "an integer",
+    i64 => "an integer",
+    i128 => "an integer",
+    isize => "an integer",
+    u8 => "an integer",
+    u16 => "an integer",
+    u32 => "an integer",
+    u64 => "an integer",
+    u128 => "an integer",
+    usize => "an integer",
+    f32 => "a number",
+    f64 => "a number",
+}
+
+impl ToLino for str {
+    fn to_lino(&self) -> LiNo<String> {
+        if self.is_empty() {
+            empty()
+        } else {
+            LiNo::Ref(self.to_string())
+        }
+    }
+}
+
+impl ToLino for String {
+    fn to_lino(&self) -> LiNo<String> {
+        self.as_str().to_lino()
+    }
+}
+
+impl FromLino for String {
+    fn from_lino(lino: &LiNo<String>) -> Result<Self> {
+        match lino {
+            LiNo::Ref(value) => Ok(value.clone()),
+            _ if is_empty(lino) => Ok(String::new()),
+            _ => Err(Error::new("expected a string, found a link")),
+        }
+    }
+}
+
+impl ToLino for () {
+    fn to_lino(&self) -> LiNo<String> {
+        empty()
+    }
+}
+
+impl FromLino for () {
+    fn from_lino(lino: &LiNo<String>) -> Result<Self> {
+        if is_empty(lino) {
+            Ok(())
+        } else {
+            Err(Error::new("expected an empty link `()`"))
+        }
+    }
+}
+
+impl<T: ToLino> ToLino for Option<T> {
+    fn to_lino(&self) -> LiNo<String> {
+        match self {
+            Some(value) => value.to_lino(),
+            None => empty(),
+        }
+    }
+}
+
+impl<T: FromLino> FromLino for Option<T> {
+    fn from_lino(lino: &LiNo<String>) -> Result<Self> {
+        if is_empty(lino) {
+            Ok(None)
+        } else {
+            T::from_lino(lino).map(Some)
+        }
+    }
+}
+
+impl<T: ToLino + ?Sized> ToLino for Box<T> {
+    fn to_lino(&self) -> LiNo<String> {
+        (**self).to_lino()
+    }
+}
+
+impl<T: FromLino> FromLino for Box<T> {
+    fn from_lino(lino: &LiNo<String>) -> Result<Self> {
+        T::from_lino(lino).map(Box::new)
+    }
+}
+
+impl<T: ToLino + ?Sized> ToLino for &T {
+    fn to_lino(&self) -> LiNo<String> {
+        (**self).to_lino()
+    }
+}
+
+impl<T: ToLino> ToLino for [T] {
+    fn to_lino(&self) -> LiNo<String> {
+        LiNo::Link {
+            id: None,
+            values: self.iter().map(ToLino::to_lino).collect(),
+        }
+    }
+}
+
+impl<T: ToLino> ToLino for Vec<T> {
+    fn to_lino(&self) -> LiNo<String> {
+        self.as_slice().to_lino()
+    }
+}
+
+impl<T: FromLino> FromLino for Vec<T> {
+    fn from_lino(lino: &LiNo<String>) -> Result<Self> {
+        match lino {
+            LiNo::Ref(_) => Ok(vec![T::from_lino(lino)?]),
+            LiNo::Link { id: None, values } => values.iter().map(T::from_lino).collect(),
+            LiNo::Link { id: Some(id), .. } => Err(Error::new(format!("expected a sequence, found the link `{}`", id))),
+        }
+    }
+}
+
+impl<T: ToLino> ToLino for BTreeMap<String, T> {
+    fn to_lino(&self) -> LiNo<String> {
+        LiNo::Link {
+            id: None,
+            values: self.iter().map(|(key, value)| field(key, value)).collect(),
+        }
+    }
+}
+
+impl<T: FromLino> FromLino for BTreeMap<String, T> {
+    fn from_lino(lino: &LiNo<String>) -> Result<Self> {
+        lino.values()
+            .iter()
+            .map(|entry| match entry {
+                LiNo::Link { id: Some(key), values } => Ok((key.clone(), T::from_lino(&content(values))?)),
+                _ => Err(Error::new("expected a `key: value` link")),
+            })
+            .collect()
+    }
+}
+
+impl ToLino for LiNo<String> {
+    fn to_lino(&self) -> LiNo<String> {
+        self.clone()
+    }
+}
+
+impl FromLino for LiNo<String> {
+    fn from_lino(lino: &LiNo<String>) -> Result<Self> {
+        Ok(lino.clone())
+    }
+}
+
+// Helpers used by the code generated by `#[derive(ToLino, FromLino)]` and
+// `crate::codegen`.
+
+/// The `(name: value)` link of a field.
+#[doc(hidden)]
+pub fn field<T: ToLino + ?Sized>(name: &str, value: &T) -> LiNo<String> {
+    LiNo::Link {
+        id: Some(name.to_string()),
+        values: vec![value.to_lino()],
+    }
+}
+
+/// The values a flattened field adds to the link of its parent.
+#[doc(hidden)]
+pub fn splice<T: ToLino + ?Sized>(value: &T) -> Vec<LiNo<String>> {
+    match value.to_lino() {
+        LiNo::Link { values, .. } => values,
+        lino => vec![lino],
+    }
+}
+
+/// The id a value is written as.
+#[doc(hidden)]
+pub fn to_id<T: ToLino + ?Sized>(value: &T) -> String {
+    match value.to_lino() {
+        LiNo::Ref(id) => id,
+        lino => lino.format(false),
+    }
+}
+
+/// Reads a value from the id of a link.
+#[doc(hidden)]
+pub fn from_id<T: FromLino>(lino: &LiNo<String>, ty: &str) -> Result<T> {
+    match lino.id() {
+        Some(id) => T::from_lino(&LiNo::Ref(id.clone())),
+        None => Err(Error::new(format!("expected a link with an id for `{}`", ty))),
+    }
+}
+
+// A single value stands for itself; several values are read as a sequence
+fn content(values: &[LiNo<String>]) -> Cow<'_, LiNo<String>> {
+    match values {
+        [value] => Cow::Borrowed(value),
+        _ => Cow::Owned(LiNo::Link {
+            id: None,
+            values: values.to_vec(),
+        }),
+    }
+}
+
+/// The values of the link of a struct.
+#[doc(hidden)]
+pub fn fields<'a>(lino: &'a LiNo<String>, ty: &str) -> Result<&'a [LiNo<String>]> {
+    match lino {
+        LiNo::Link { values, .. } => Ok(values),
+        LiNo::Ref(value) => Err(Error::new(format!("expected a link for `{}`, found `{}`", ty, value))),
+    }
+}
+
+/// Reads the field `name` from the values of a link. A missing field is read
+/// from `()`, so missing options and sequences are `None` and empty.
+#[doc(hidden)]
+pub fn read_field<T: FromLino>(values: &[LiNo<String>], name: &str) -> Result<T> {
+    let found = values.iter().find_map(|value| match value {
+        LiNo::Link { id: Some(id), values } if id == name => Some(values),
+        _ => None,
+    });
+    match found {
+        Some(values) => {
+            T::from_lino(&content(values)).map_err(|error| Error::new(format!("field `{}`: {}", name, error.message())))
+        }
+        None => T::from_lino(&empty()).map_err(|_| Error::new(format!("missing field `{}`", name))),
+    }
+}
+
+/// The values of a tuple struct or variant, which must have `len` elements.
+#[doc(hidden)]
+pub fn elements<'a>(values: &'a [LiNo<String>], len: usize, ty: &str) -> Result<&'a [LiNo<String>]> {
+    if values.len() == len {
+        Ok(values)
+    } else {
+        Err(Error::new(format!(
+            "expected {} values for `{}`, found {}",
+            len,
+            ty,
+            values.len()
+        )))
+    }
+}
+
+/// Reads the value of a newtype variant.
+#[doc(hidden)]
+pub fn newtype<T: FromLino>(values: &[LiNo<String>]) -> Result<T> {
+    T::from_lino(&content(values))
+}
+
+/// The name and values of an enum variant.
+#[doc(hidden)]
+pub fn variant<'a>(lino: &'a LiNo<String>, ty: &str) -> Result<(&'a str, &'a [LiNo<String>])> {
+    match lino {
+        LiNo::Ref(name) => Ok((name, &[])),
+        LiNo::Link { id: Some(name), values } => Ok((name, values)),
+        LiNo::Link { id: None, .. } => Err(Error::new(format!("expected a variant of `{}`", ty))),
+    }
+}
+
+/// Error for a variant name that is not part of the enum.
+#[doc(hidden)]
+pub fn unknown_variant(name: &str, ty: &str) -> Error {
+    Error::new(format!("unknown variant `{}` of `{}`", name, ty))
+}
+
+/// The values of a link that must have the id `id`.
+#[doc(hidden)]
+pub fn expect_id<'a>(lino: &'a LiNo<String>, id: &str) -> Result<&'a [LiNo<String>]> {
+    match lino {
+        LiNo::Link { id: Some(own), values } if own == id => Ok(values),
+        _ => Err(Error::new(format!("expected a `{}` link, found `{}`", id, lino))),
+    }
+}
+
+/// The value that is a link with the id `id`.
+#[doc(hidden)]
+pub fn find<'a>(values: &'a [LiNo<String>], id: &str) -> Option<&'a LiNo<String>> {
+    values.iter().find(|value| value.id().is_some_and(|own| own == id))
+}
+
+/// Reads the value that is a link with the id `id`.
+#[doc(hidden)]
+pub fn read_link<T: FromLino>(values: &[LiNo<String>], id: &str) -> Result<T> {
+    match find(values, id) {
+        Some(link) => T::from_lino(link),
+        None => Err(Error::new(format!("missing `{}`", id))),
+    }
+}
@@ -0,0 +1,219 @@
+//! Tabular import and export of links as CSV or TSV.
+//!
+//! Every row becomes a link and every cell becomes one of its values. Cells
+//! hold Links Notation, so `mama`, `'New York'` and `(a b)` become a reference,
+//! a quoted reference and a nested link; an empty cell becomes `()`. One
+//! column can be used as the link id:
+//!
+//! ```text
+//! id,source,target        (loves: papa mama)
+//! loves,papa,mama    <->  (likes: son 'ice cream')
+//! likes,son,'ice cream'
+//! ```
+//!
+//! Cells are quoted as CSV when they contain the delimiter, quotes or line
+//! breaks, and references are quoted as Links Notation when they contain
+//! spaces or special characters, so both sides read back unchanged.
+
+use ::csv::{ReaderBuilder, WriterBuilder};
+
+use crate::error::{Error, Result};
+use crate::{check_representable, format_links, parse_lino_to_links, LiNo};
+
+/// Options for reading and writing tables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    /// Field delimiter, `b','` for CSV and `b'\t'` for TSV.
+    pub delimiter: u8,
+    /// Whether the first row is a header. It is skipped when reading and
+    /// written when writing.
+    pub has_header: bool,
+    /// Column names written as the header. When empty, the id column is named
+    /// `id` and the other columns `value1`, `value2` and so on.
+    pub header: Vec<String>,
+    /// Column holding the link id, if any.
+    pub id_column: Option<usize>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: b',',
+            has_header: false,
+            header: vec![],
+            id_column: None,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Options for comma-separated values.
+    pub fn csv() -> Self {
+        Self::default()
+    }
+
+    /// Options for tab-separated values.
+    pub fn tsv() -> Self {
+        CsvOptions {
+            delimiter: b'\t',
+            ..Self::default()
+        }
+    }
+
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    pub fn with_header_names<S: Into<String>>(mut self, names: impl IntoIterator<Item = S>) -> Self {
+        self.header = names.into_iter().map(Into::into).collect();
+        self.has_header = true;
+        self
+    }
+
+    pub fn with_id_column(mut self, column: usize) -> Self {
+        self.id_column = Some(column);
+        self
+    }
+}
+
+fn parse_cell(cell: &str) -> std::result::Result<LiNo<String>, String> {
+    if cell.trim().is_empty() {
+        return Ok(LiNo::Link { id: None, values: vec![] });
+    }
+    let mut links = parse_lino_to_links(cell)?;
+    match links.len() {
+        1 => Ok(links.remove(0)),
+        count => Err(format!("expected a single link, found {}", count)),
+    }
+}
+
+/// Reads every row of a table as a link.
+pub fn csv_to_lino(input: &str, options: &CsvOptions) -> Result<Vec<LiNo<String>>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.has_header)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+
+    let mut links = vec![];
+    for record in reader.records() {
+        let record = record.map_err(|error| Error::new(error.to_string()))?;
+        let line = record.position().map_or(0, |position| position.line());
+        let field_error =
+            |field: usize, message: String| Error::new(format!("line {}, field {}: {}", line, field + 1, message));
+
+        let mut id = None;
+        let mut values = vec![];
+        for (field, cell) in record.iter().enumerate() {
+            if Some(field) == options.id_column {
+                match parse_cell(cell).map_err(|message| field_error(field, message))? {
+                    LiNo::Ref(reference) => id = Some(reference),
+                    _ => return Err(field_error(field, "the id must be a single reference".to_string())),
+                }
+            } else {
+                values.push(parse_cell(cell).map_err(|message| field_error(field, message))?);
+            }
+        }
+        if options.id_column.is_some() && id.is_none() {
+            return Err(Error::new(format!("line {}: missing id column", line)));
+        }
+        links.push(LiNo::Link { id, values });
+    }
+    Ok(links)
+}
+
+fn format_cell<T: ToString>(value: &LiNo<T>) -> String {
+    match value {
+        LiNo::Link { id: None, values } if values.is_empty() => String::new(),
+        value => value.format(false),
+    }
+}
+
+/// Writes links as table rows. Every link must have the same number of values
+/// and, when an id column is used, an id.
+pub fn lino_to_csv<T: ToString>(links: &[LiNo<T>], options: &CsvOptions) -> Result<String> {
+    let mut writer = WriterBuilder::new().delimiter(options.delimiter).from_writer(vec![]);
+    let mut width = None;
+
+    for (index, link) in links.iter().enumerate() {
+        check_representable(link)?;
+        let (id, values) = match link {
+            LiNo::Link { id, values } => (id.as_ref(), values.as_slice()),
+            reference => (None, std::slice::from_ref(reference)),
+        };
+
+        let mut row: Vec<String> = values.iter().map(format_cell).collect();
+        match (options.id_column, id) {
+            (Some(column), Some(id)) if column <= row.len() => {
+                row.insert(column, LiNo::Ref(id.to_string()).format(false))
+            }
+            (Some(column), Some(_)) => {
+                return Err(Error::new(format!("link {} has no column {}", index + 1, column + 1)))
+            }
+            (Some(_), None) => return Err(Error::new(format!("link {} has no id", index + 1))),
+            (None, Some(_)) => {
+                return Err(Error::new(format!(
+                    "link {} has an id but no id column is configured",
+                    index + 1
+                )))
+            }
+            (None, None) => {}
+        }
+
+        match width {
+            None if options.has_header => {
+                let header = if options.header.is_empty() {
+                    let mut value = 0;
+                    (0..row.len())
+                        .map(|column| {
+                            if Some(column) == options.id_column {
+                                "id".to_string()
+                            } else {
+                                value += 1;
+                                format!("value{}", value)
+                            }
+                        })
+                        .collect()
+                } else if options.header.len() == row.len() {
+                    options.header.clone()
+                } else {
+                    return Err(Error::new(format!(
+                        "header has {} columns but links have {}",
+                        options.header.len(),
+                        row.len()
+                    )));
+                };
+                writer.write_record(&header).map_err(|error| Error::new(error.to_string()))?;
+            }
+            Some(width) if width != row.len() => {
+                return Err(Error::new(format!(
+                    "link {} has {} columns, expected {}",
+                    index + 1,
+                    row.len(),
+                    width
+                )))
+            }
+            _ => {}
+        }
+        width = Some(row.len());
+        writer.write_record(&row).map_err(|error| Error::new(error.to_string()))?;
+    }
+
+    if width.is_none() && options.has_header && !options.header.is_empty() {
+        writer.write_record(&options.header).map_err(|error| Error::new(error.to_string()))?;
+    }
+
+    let bytes = writer.into_inner().map_err(|error| Error::new(error.to_string()))?;
+    String::from_utf8(bytes).map_err(|error| Error::new(error.to_string()))
+}
+
+/// Converts a table into a Links Notation document, one link per row.
+pub fn csv_to_text(input: &str, options: &CsvOptions) -> Result<String> {
+    Ok(format_links(&csv_to_lino(input, options)?, false))
+}
+
+/// Converts the links of a Links Notation document into a table.
+pub fn text_to_csv(document: &str, options: &CsvOptions) -> Result<String> {
+    lino_to_csv(&parse_lino_to_links(document).map_err(Error::new)?, options)
+}
@@ -0,0 +1,461 @@
+//! Serde deserializer reading Links Notation documents.
+//!
+//! Deserialization works on the parser's syntax tree, so both indented blocks
+//! and parenthesized links can describe the same value:
+//!
+//! ```text
+//! server:
+//!   host: localhost
+//!   port: 8080
+//! server: (host: localhost) (port: 8080)
+//! ```
+//!
+//! - structs and maps are read from `key: value` links, one entry per link;
+//! - sequences are read from the values of a link, `(1 2 3)`, from the values
+//!   following a key, `key: 1 2 3`, or from the lines of a block;
+//! - a single reference is accepted where a sequence is expected;
+//! - `None`, `()` and empty strings are read from the empty link `()`;
+//! - enum variants are written as `Unit`, `Newtype: value`, `Tuple: a b` and
+//!   `Struct: (field: value)`.
+//!
+//! At the top level of a document each line is an entry of a struct or map,
+//! or an element of a sequence. Errors carry the line and column of the link
+//! that could not be deserialized.
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Visitor};
+
+use crate::error::{Error, Result};
+use crate::parser::{self, Link};
+
+/// Deserializes an instance of `T` from a Links Notation document.
+pub fn from_str<T: DeserializeOwned>(document: &str) -> Result<T> {
+    let links = parse(document)?;
+    let items: Vec<&Link> = links.iter().collect();
+    let value = if items.is_empty() {
+        Value { node: Node::Empty, offset: 0 }
+    } else {
+        Value { offset: items[0].offset, node: Node::Items(items) }
+    };
+    T::deserialize(Deserializer { document, value })
+}
+
+fn parse(document: &str) -> Result<Vec<Link>> {
+    if document.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    match parser::parse_document(document) {
+        Ok((_, links)) => Ok(links),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            Err(Error::new("Parse error").at(document, document.len() - e.input.len()))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(Error::new("Parse error: incomplete input").at(document, document.len())),
+    }
+}
+
+#[derive(Clone)]
+enum Node<'a> {
+    Empty,
+    Scalar(&'a str),
+    Link(&'a Link),
+    Items(Vec<&'a Link>),
+}
+
+#[derive(Clone)]
+struct Value<'a> {
+    node: Node<'a>,
+    offset: usize,
+}
+
+fn is_entry(link: &Link) -> bool {
+    link.id.is_some() && (link.is_indented_id || !link.values.is_empty() || !link.children.is_empty())
+}
+
+impl<'a> Value<'a> {
+    fn from_link(link: &'a Link) -> Self {
+        let node = match &link.id {
+            Some(id) if !is_entry(link) => Node::Scalar(id),
+            _ => Node::Link(link),
+        };
+        Value { node, offset: link.offset }
+    }
+
+    // The value of a `key: value` link: its values followed by its indented children
+    fn of_entry(link: &'a Link) -> Self {
+        let items: Vec<&Link> = link.values.iter().chain(link.children.iter()).collect();
+        match items.len() {
+            0 => Value { node: Node::Empty, offset: link.offset },
+            1 => Value::from_link(items[0]),
+            _ => Value { offset: items[0].offset, node: Node::Items(items) },
+        }
+    }
+
+    fn single(&self) -> Option<Value<'a>> {
+        match &self.node {
+            Node::Items(items) if items.len() == 1 => Some(Value::from_link(items[0])),
+            _ => None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match &self.node {
+            Node::Empty => true,
+            Node::Link(link) => link.id.is_none() && link.values.is_empty() && link.children.is_empty(),
+            Node::Items(items) => items.is_empty(),
+            Node::Scalar(_) => false,
+        }
+    }
+
+    fn scalar(&self) -> Result<&'a str> {
+        match &self.node {
+            Node::Scalar(value) => Ok(value),
+            Node::Items(_) if self.single().is_some() => self.single().unwrap().scalar(),
+            _ => Err(Error::new("expected a reference, found a link")),
+        }
+    }
+
+    fn elements(&self) -> Vec<Value<'a>> {
+        match &self.node {
+            Node::Empty => vec![],
+            Node::Scalar(_) => vec![self.clone()],
+            Node::Link(link) if link.id.is_some() => vec![self.clone()],
+            Node::Link(link) => link.values.iter().chain(link.children.iter()).map(Value::from_link).collect(),
+            Node::Items(items) => items.iter().map(|item| Value::from_link(item)).collect(),
+        }
+    }
+
+    fn entries(&self) -> Result<Vec<&'a Link>> {
+        if let Some(single) = self.single() {
+            return single.entries();
+        }
+        let links: Vec<&Link> = match &self.node {
+            Node::Empty => vec![],
+            Node::Scalar(value) => {
+                return Err(Error::new(format!("expected `key: value` links, found reference `{}`", value)))
+            }
+            Node::Link(link) if link.id.is_some() => vec![*link],
+            Node::Link(link) => link.values.iter().chain(link.children.iter()).collect(),
+            Node::Items(items) => items.clone(),
+        };
+        for link in &links {
+            if !is_entry(link) {
+                return Err(Error::new("expected a `key: value` link"));
+            }
+        }
+        Ok(links)
+    }
+
+    fn is_map(&self) -> bool {
+        match &self.node {
+            Node::Link(link) if link.id.is_some() => true,
+            Node::Link(_) | Node::Items(_) => {
+                let elements = self.elements();
+                !elements.is_empty()
+                    && elements.iter().all(|e| matches!(e.node, Node::Link(link) if link.id.is_some()))
+            }
+            _ => false,
+        }
+    }
+
+    fn variant(&self) -> Result<(Value<'a>, Value<'a>)> {
+        match &self.node {
+            Node::Scalar(_) => Ok((self.clone(), Value { node: Node::Empty, offset: self.offset })),
+            Node::Link(link) => match &link.id {
+                Some(id) => Ok((Value { node: Node::Scalar(id), offset: link.offset }, Value::of_entry(link))),
+                None if link.values.len() == 1 && link.children.is_empty() => {
+                    Value::from_link(&link.values[0]).variant()
+                }
+                None => Err(Error::new("expected an enum variant")),
+            },
+            Node::Items(_) if self.single().is_some() => self.single().unwrap().variant(),
+            _ => Err(Error::new("expected an enum variant")),
+        }
+    }
+}
+
+struct Deserializer<'a> {
+    document: &'a str,
+    value: Value<'a>,
+}
+
+impl<'a> Deserializer<'a> {
+    fn new(document: &'a str, value: Value<'a>) -> Self {
+        Deserializer { document, value }
+    }
+
+    fn locate(&self) -> impl Fn(Error) -> Error + 'a {
+        let document = self.document;
+        let offset = self.value.offset;
+        move |error| error.at(document, offset)
+    }
+
+    fn parse_scalar<T: std::str::FromStr>(&self, expected: &str) -> Result<T> {
+        let value = self.value.scalar().map_err(self.locate())?;
+        value
+            .parse()
+            .map_err(|_| Error::new(format!("invalid {} `{}`", expected, value)))
+            .map_err(self.locate())
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                let value: $ty = self.parse_scalar(stringify!($ty))?;
+                visitor.$visit(value).map_err(self.locate())
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let locate = self.locate();
+        if let Some(single) = self.value.single() {
+            return Deserializer::new(self.document, single).deserialize_any(visitor);
+        }
+        if self.value.is_empty() {
+            visitor.visit_unit().map_err(locate)
+        } else if let Node::Scalar(value) = self.value.node {
+            visitor.visit_str(value).map_err(locate)
+        } else if self.value.is_map() {
+            self.deserialize_map(visitor)
+        } else {
+            self.deserialize_seq(visitor)
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool(bool),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+        deserialize_char => visit_char(char),
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let locate = self.locate();
+        if self.value.is_empty() {
+            return visitor.visit_str("").map_err(locate);
+        }
+        let value = self.value.scalar().map_err(&locate)?;
+        visitor.visit_str(value).map_err(locate)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let locate = self.locate();
+        if self.value.is_empty() {
+            visitor.visit_none().map_err(locate)
+        } else {
+            visitor.visit_some(self).map_err(locate)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let locate = self.locate();
+        if self.value.is_empty() {
+            visitor.visit_unit().map_err(locate)
+        } else {
+            Err(locate(Error::new("expected an empty link `()`")))
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        let locate = self.locate();
+        visitor.visit_newtype_struct(self).map_err(locate)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let locate = self.locate();
+        let elements = self.value.elements();
+        visitor
+            .visit_seq(SeqAccess {
+                document: self.document,
+                elements: elements.into_iter(),
+            })
+            .map_err(locate)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let locate = self.locate();
+        let entries = self.value.entries().map_err(&locate)?;
+        visitor
+            .visit_map(MapAccess {
+                document: self.document,
+                entries: entries.into_iter(),
+                value: None,
+            })
+            .map_err(locate)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let locate = self.locate();
+        let (variant, content) = self.value.variant().map_err(&locate)?;
+        visitor
+            .visit_enum(EnumAccess {
+                document: self.document,
+                variant,
+                content,
+            })
+            .map_err(locate)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+struct SeqAccess<'a> {
+    document: &'a str,
+    elements: std::vec::IntoIter<Value<'a>>,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for SeqAccess<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.elements.next() {
+            Some(value) => seed.deserialize(Deserializer::new(self.document, value)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
+
+struct MapAccess<'a> {
+    document: &'a str,
+    entries: std::vec::IntoIter<&'a Link>,
+    value: Option<Value<'a>>,
+}
+
+impl<'de, 'a> de::MapAccess<'de> for MapAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.entries.next() {
+            Some(entry) => {
+                self.value = Some(Value::of_entry(entry));
+                let key = Value {
+                    node: Node::Scalar(entry.id.as_deref().unwrap_or_default()),
+                    offset: entry.offset,
+                };
+                seed.deserialize(Deserializer::new(self.document, key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::new("next_value_seed called before next_key_seed"))?;
+        seed.deserialize(Deserializer::new(self.document, value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct EnumAccess<'a> {
+    document: &'a str,
+    variant: Value<'a>,
+    content: Value<'a>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumAccess<'a> {
+    type Error = Error;
+    type Variant = Deserializer<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let variant = seed.deserialize(Deserializer::new(self.document, self.variant))?;
+        Ok((variant, Deserializer::new(self.document, self.content)))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for Deserializer<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        if self.value.is_empty() {
+            Ok(())
+        } else {
+            Err(self.locate()(Error::new("unexpected values for a unit variant")))
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,311 @@
+//! Export of links as Graphviz DOT, GraphML and Mermaid diagrams.
+//!
+//! References and link ids become nodes. Links are drawn depending on their
+//! number of values:
+//!
+//! - a doublet `(id: source target)` is a plain edge from `source` to
+//!   `target`, labelled with its id;
+//! - a link with a single value `(id: value)` is an edge from `id` to `value`;
+//! - a link with no values `(id)` is a node;
+//! - any other link is a hyperedge, named after its id, connected to each of
+//!   its values in order. DOT and Mermaid draw it as a record node with one
+//!   field per value; GraphML writes it as a `<hyperedge>`.
+//!
+//! A link nested as a value of another link is always drawn as a hyperedge,
+//! so it can be pointed at. Links without an id are named `link1`, `link2`
+//! and so on, skipping names that are already used.
+
+use std::collections::HashSet;
+
+use crate::LiNo;
+
+struct Hyperedge {
+    name: String,
+    label: Option<String>,
+    members: Vec<String>,
+}
+
+struct Edge {
+    source: String,
+    target: String,
+    label: Option<String>,
+}
+
+#[derive(Default)]
+struct Graph {
+    nodes: Vec<String>,
+    edges: Vec<Edge>,
+    hyperedges: Vec<Hyperedge>,
+    used: HashSet<String>,
+    generated: usize,
+}
+
+fn collect_names<T: ToString>(lino: &LiNo<T>, names: &mut HashSet<String>) {
+    match lino {
+        LiNo::Ref(value) => {
+            names.insert(value.to_string());
+        }
+        LiNo::Link { id, values } => {
+            if let Some(id) = id {
+                names.insert(id.to_string());
+            }
+            for value in values {
+                collect_names(value, names);
+            }
+        }
+    }
+}
+
+impl Graph {
+    fn new<T: ToString>(links: &[LiNo<T>]) -> Self {
+        let mut graph = Graph::default();
+        for link in links {
+            collect_names(link, &mut graph.used);
+        }
+        for link in links {
+            graph.add(link);
+        }
+        graph
+    }
+
+    fn node(&mut self, name: String) -> String {
+        if !self.nodes.contains(&name) {
+            self.nodes.push(name.clone());
+        }
+        name
+    }
+
+    fn generate_name(&mut self) -> String {
+        loop {
+            self.generated += 1;
+            let name = format!("link{}", self.generated);
+            if self.used.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    // Adds a top-level link
+    fn add<T: ToString>(&mut self, lino: &LiNo<T>) {
+        match lino {
+            LiNo::Ref(value) => {
+                self.node(value.to_string());
+            }
+            LiNo::Link { id, values } => match (id, values.as_slice()) {
+                (label, [source, target]) => {
+                    let source = self.endpoint(source);
+                    let target = self.endpoint(target);
+                    let label = label.as_ref().map(ToString::to_string);
+                    self.edges.push(Edge { source, target, label });
+                }
+                (Some(id), [value]) => {
+                    let source = self.node(id.to_string());
+                    let target = self.endpoint(value);
+                    self.edges.push(Edge { source, target, label: None });
+                }
+                (Some(id), []) => {
+                    self.node(id.to_string());
+                }
+                _ => {
+                    self.hyperedge(lino);
+                }
+            },
+        }
+    }
+
+    // Returns the node a value points at, adding nested links as hyperedges
+    fn endpoint<T: ToString>(&mut self, lino: &LiNo<T>) -> String {
+        match lino {
+            LiNo::Ref(value) => self.node(value.to_string()),
+            link => self.hyperedge(link),
+        }
+    }
+
+    fn hyperedge<T: ToString>(&mut self, lino: &LiNo<T>) -> String {
+        let (id, values) = match lino {
+            LiNo::Link { id, values } => (id, values),
+            LiNo::Ref(value) => return self.node(value.to_string()),
+        };
+        let label = id.as_ref().map(ToString::to_string);
+        let name = match &label {
+            Some(label) => label.clone(),
+            None => self.generate_name(),
+        };
+        let members = values.iter().map(|value| self.endpoint(value)).collect();
+        self.hyperedges.push(Hyperedge {
+            name: name.clone(),
+            label,
+            members,
+        });
+        name
+    }
+
+    fn is_hyperedge(&self, name: &str) -> bool {
+        self.hyperedges.iter().any(|hyperedge| hyperedge.name == name)
+    }
+}
+
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+// Escapes a field of a record label, which is written inside a quoted string
+// as is: record separators, quotes and backslashes get a single backslash
+fn dot_record_field(value: &str) -> String {
+    let mut result = String::new();
+    for c in value.chars() {
+        match c {
+            '\n' => result.push_str("\\n"),
+            _ if "{}|<>\"\\ ".contains(c) => {
+                result.push('\\');
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Writes links as a Graphviz DOT digraph.
+pub fn to_dot<T: ToString>(links: &[LiNo<T>]) -> String {
+    let graph = Graph::new(links);
+    let mut output = String::from("digraph links {\n");
+    for node in &graph.nodes {
+        if !graph.is_hyperedge(node) {
+            output.push_str(&format!("  {};\n", dot_string(node)));
+        }
+    }
+    for hyperedge in &graph.hyperedges {
+        let mut fields = vec![dot_record_field(hyperedge.label.as_deref().unwrap_or(""))];
+        for (index, member) in hyperedge.members.iter().enumerate() {
+            fields.push(format!("<v{}> {}", index, dot_record_field(member)));
+        }
+        output.push_str(&format!(
+            "  {} [shape=record, label=\"{{{}}}\"];\n",
+            dot_string(&hyperedge.name),
+            fields.join("|")
+        ));
+        for (index, member) in hyperedge.members.iter().enumerate() {
+            output.push_str(&format!(
+                "  {}:v{} -> {};\n",
+                dot_string(&hyperedge.name),
+                index,
+                dot_string(member)
+            ));
+        }
+    }
+    for edge in &graph.edges {
+        output.push_str(&format!("  {} -> {}", dot_string(&edge.source), dot_string(&edge.target)));
+        if let Some(label) = &edge.label {
+            output.push_str(&format!(" [label={}]", dot_string(label)));
+        }
+        output.push_str(";\n");
+    }
+    output.push_str("}\n");
+    output
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Writes links as a GraphML document.
+pub fn to_graphml<T: ToString>(links: &[LiNo<T>]) -> String {
+    let graph = Graph::new(links);
+    let mut output = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"label\" for=\"all\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        "  <graph id=\"links\" edgedefault=\"directed\">\n"
+    ));
+    let pointed_at: HashSet<&str> = graph
+        .edges
+        .iter()
+        .flat_map(|edge| [edge.source.as_str(), edge.target.as_str()])
+        .chain(graph.hyperedges.iter().flat_map(|hyperedge| hyperedge.members.iter().map(String::as_str)))
+        .collect();
+    let hyperedge_nodes = graph
+        .hyperedges
+        .iter()
+        .map(|hyperedge| &hyperedge.name)
+        .filter(|name| pointed_at.contains(name.as_str()) && !graph.nodes.contains(name));
+    for node in graph.nodes.iter().chain(hyperedge_nodes) {
+        output.push_str(&format!(
+            "    <node id=\"{0}\"><data key=\"label\">{0}</data></node>\n",
+            xml_escape(node)
+        ));
+    }
+    for edge in &graph.edges {
+        output.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\"",
+            xml_escape(&edge.source),
+            xml_escape(&edge.target)
+        ));
+        match &edge.label {
+            Some(label) => output.push_str(&format!("><data key=\"label\">{}</data></edge>\n", xml_escape(label))),
+            None => output.push_str("/>\n"),
+        }
+    }
+    for hyperedge in &graph.hyperedges {
+        output.push_str("    <hyperedge>");
+        if let Some(label) = &hyperedge.label {
+            output.push_str(&format!("<data key=\"label\">{}</data>", xml_escape(label)));
+        }
+        for member in &hyperedge.members {
+            output.push_str(&format!("<endpoint node=\"{}\"/>", xml_escape(member)));
+        }
+        output.push_str("</hyperedge>\n");
+    }
+    output.push_str("  </graph>\n</graphml>\n");
+    output
+}
+
+fn mermaid_text(value: &str) -> String {
+    value.replace('"', "#quot;").replace('\n', "<br/>")
+}
+
+/// Writes links as a Mermaid flowchart.
+pub fn to_mermaid<T: ToString>(links: &[LiNo<T>]) -> String {
+    let graph = Graph::new(links);
+    let mut names: Vec<&str> = graph.nodes.iter().map(String::as_str).collect();
+    for hyperedge in &graph.hyperedges {
+        if !names.contains(&hyperedge.name.as_str()) {
+            names.push(&hyperedge.name);
+        }
+    }
+    let key = |name: &str| format!("n{}", names.iter().position(|node| *node == name).unwrap_or(0) + 1);
+
+    let mut output = String::from("flowchart LR\n");
+    for name in &names {
+        match graph.hyperedges.iter().find(|hyperedge| hyperedge.name == *name) {
+            Some(hyperedge) => {
+                let mut fields = vec![];
+                fields.extend(hyperedge.label.as_deref());
+                fields.extend(hyperedge.members.iter().map(String::as_str));
+                output.push_str(&format!("  {}[\"{}\"]\n", key(name), mermaid_text(&fields.join(" | "))));
+            }
+            None => output.push_str(&format!("  {}[\"{}\"]\n", key(name), mermaid_text(name))),
+        }
+    }
+    for hyperedge in &graph.hyperedges {
+        for (index, member) in hyperedge.members.iter().enumerate() {
+            output.push_str(&format!("  {} -->|{}| {}\n", key(&hyperedge.name), index + 1, key(member)));
+        }
+    }
+    for edge in &graph.edges {
+        match &edge.label {
+            Some(label) => output.push_str(&format!(
+                "  {} -->|\"{}\"| {}\n",
+                key(&edge.source),
+                mermaid_text(label),
+                key(&edge.target)
+            )),
+            None => output.push_str(&format!("  {} --> {}\n", key(&edge.source), key(&edge.target))),
+        }
+    }
+    output
+}
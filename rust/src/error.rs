@@ -0,0 +1,75 @@
+use std::fmt;
+
+use crate::parser;
+
+/// Error produced while converting between Links Notation and other representations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    message: String,
+    location: Option<(usize, usize)>,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Error {
+            message: message.into(),
+            location: None,
+        }
+    }
+
+    /// Attaches the line and column of `offset` in `document`, unless the error
+    /// already points to a more specific location.
+    pub(crate) fn at(mut self, document: &str, offset: usize) -> Self {
+        if self.location.is_none() {
+            self.location = Some(parser::line_column(document, offset));
+        }
+        self
+    }
+
+    #[cfg(feature = "yaml")]
+    pub(crate) fn with_location(mut self, line: usize, column: usize) -> Self {
+        self.location = Some((line, column));
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// 1-based line of the document the error refers to, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.location.map(|(line, _)| line)
+    }
+
+    /// 1-based column of the document the error refers to, if known.
+    pub fn column(&self) -> Option<usize> {
+        self.location.map(|(_, column)| column)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(f, "{} at line {}, column {}", self.message, line, column),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::new(msg.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::new(msg.to_string())
+    }
+}
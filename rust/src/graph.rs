@@ -0,0 +1,220 @@
+//! Resolution of references into a graph of links.
+//!
+//! [`LinkGraph`] stores every link of a document, including nested ones, under
+//! an index in document order. References that name the id of a link point to
+//! that link, wherever it is defined, so forward references and cycles such as
+//!
+//! ```text
+//! (a: b)
+//! (b: a)
+//! ```
+//!
+//! resolve to links that point at each other. References to names that no
+//! link defines are kept as plain names and reported by
+//! [`LinkGraph::undefined`]; ids defined more than once are reported by
+//! [`LinkGraph::duplicates`], and references to them resolve to the first
+//! definition.
+
+use std::collections::HashMap;
+
+use crate::{parse_lino_to_links, LiNo};
+
+/// A value of a resolved link.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedValue {
+    /// A link nested in place, by index.
+    Link(usize),
+    /// A reference, with the index of the link it names if there is one.
+    Reference { name: String, target: Option<usize> },
+}
+
+impl ResolvedValue {
+    /// Index of the link this value points to, if any.
+    pub fn target(&self) -> Option<usize> {
+        match self {
+            ResolvedValue::Link(index) => Some(*index),
+            ResolvedValue::Reference { target, .. } => *target,
+        }
+    }
+}
+
+/// A link of a [`LinkGraph`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedLink {
+    pub id: Option<String>,
+    pub values: Vec<ResolvedValue>,
+    /// Index of the link this one is nested in, if any.
+    pub parent: Option<usize>,
+}
+
+/// A reference to a name from one of the values of a link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Use {
+    /// Index of the link holding the reference.
+    pub link: usize,
+    /// Position of the reference among the values of that link.
+    pub position: usize,
+}
+
+/// Links with their references resolved.
+#[derive(Debug, Clone, Default)]
+pub struct LinkGraph {
+    links: Vec<ResolvedLink>,
+    roots: Vec<usize>,
+    definitions: HashMap<String, Vec<usize>>,
+    uses: HashMap<String, Vec<Use>>,
+    names: Vec<String>,
+}
+
+impl LinkGraph {
+    /// Builds the graph of `links`, treating top-level references as links
+    /// with a single value.
+    pub fn new(links: &[LiNo<String>]) -> Self {
+        let mut graph = LinkGraph::default();
+        for link in links {
+            let root = match link {
+                LiNo::Ref(_) => graph.add(None, std::slice::from_ref(link), None),
+                LiNo::Link { id, values } => graph.add(id.as_ref(), values, None),
+            };
+            graph.roots.push(root);
+        }
+        graph.resolve();
+        graph
+    }
+
+    /// Parses `document` and builds the graph of its links.
+    pub fn parse(document: &str) -> Result<Self, String> {
+        parse_lino_to_links(document).map(|links| Self::new(&links))
+    }
+
+    fn add(&mut self, id: Option<&String>, values: &[LiNo<String>], parent: Option<usize>) -> usize {
+        let index = self.links.len();
+        self.links.push(ResolvedLink {
+            id: id.cloned(),
+            values: Vec::with_capacity(values.len()),
+            parent,
+        });
+        if let Some(id) = id {
+            self.note_name(id);
+            self.definitions.entry(id.clone()).or_default().push(index);
+        }
+        for (position, value) in values.iter().enumerate() {
+            let value = match value {
+                LiNo::Ref(name) => {
+                    self.note_name(name);
+                    self.uses.entry(name.clone()).or_default().push(Use { link: index, position });
+                    ResolvedValue::Reference {
+                        name: name.clone(),
+                        target: None,
+                    }
+                }
+                LiNo::Link { id, values } => ResolvedValue::Link(self.add(id.as_ref(), values, Some(index))),
+            };
+            self.links[index].values.push(value);
+        }
+        index
+    }
+
+    fn note_name(&mut self, name: &str) {
+        if !self.definitions.contains_key(name) && !self.uses.contains_key(name) {
+            self.names.push(name.to_string());
+        }
+    }
+
+    fn resolve(&mut self) {
+        for link in &mut self.links {
+            for value in &mut link.values {
+                if let ResolvedValue::Reference { name, target } = value {
+                    *target = self.definitions.get(name).map(|indices| indices[0]);
+                }
+            }
+        }
+    }
+
+    /// Number of links, including nested ones.
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    pub fn link(&self, index: usize) -> &ResolvedLink {
+        &self.links[index]
+    }
+
+    /// All links with their indices, in document order.
+    pub fn links(&self) -> impl Iterator<Item = (usize, &ResolvedLink)> {
+        self.links.iter().enumerate()
+    }
+
+    /// Indices of the top-level links.
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    /// Index of the link defining `name`.
+    pub fn definition_of(&self, name: &str) -> Option<usize> {
+        self.definitions.get(name).map(|indices| indices[0])
+    }
+
+    /// Every reference to `name`.
+    pub fn uses_of(&self, name: &str) -> &[Use] {
+        self.uses.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Indices of the links that the values of a link point to, in order.
+    pub fn outgoing(&self, index: usize) -> Vec<usize> {
+        let mut targets = vec![];
+        for target in self.links[index].values.iter().filter_map(ResolvedValue::target) {
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+        targets
+    }
+
+    /// Indices of the links with a value pointing to a link.
+    pub fn incoming(&self, index: usize) -> Vec<usize> {
+        self.links
+            .iter()
+            .enumerate()
+            .filter(|(_, link)| link.values.iter().any(|value| value.target() == Some(index)))
+            .map(|(source, _)| source)
+            .collect()
+    }
+
+    /// Indices of the links connected to a link in either direction.
+    pub fn neighbours(&self, index: usize) -> Vec<usize> {
+        let mut neighbours = self.outgoing(index);
+        for source in self.incoming(index) {
+            if !neighbours.contains(&source) {
+                neighbours.push(source);
+            }
+        }
+        neighbours
+    }
+
+    /// Names that are referenced but not defined by any link, in order of
+    /// first appearance.
+    pub fn undefined(&self) -> Vec<&str> {
+        self.names
+            .iter()
+            .filter(|name| !self.definitions.contains_key(*name))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Ids defined by more than one link, with the indices of all their
+    /// definitions, in order of first appearance.
+    pub fn duplicates(&self) -> Vec<(&str, &[usize])> {
+        self.names
+            .iter()
+            .filter_map(|name| match self.definitions.get(name) {
+                Some(indices) if indices.len() > 1 => Some((name.as_str(), indices.as_slice())),
+                _ => None,
+            })
+            .collect()
+    }
+}
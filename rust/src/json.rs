@@ -0,0 +1,110 @@
+//! Conversion between JSON values and Links Notation.
+//!
+//! JSON values are mapped onto `LiNo<String>` as follows:
+//!
+//! | JSON                     | Links Notation                     |
+//! |--------------------------|------------------------------------|
+//! | `null`, `true`, `false`  | `null`, `true`, `false`            |
+//! | `42`, `-1.5e3`           | `42`, `-1.5e3`                     |
+//! | `"text"`                 | `text` (quoted when needed)        |
+//! | `"42"`, `"null"`, `""`   | `(string: 42)`, `(string: null)`, `(string: ())` |
+//! | `{"a": 1, "b": [2, 3]}`  | `((a: 1) (b: (2 3)))`              |
+//! | `{}`                     | `()`                               |
+//! | `[1, 2]`                 | `(1 2)`                            |
+//! | `[1]`, `[]`              | `(array: 1)`, `(array: ())`        |
+//!
+//! Strings that would read back as another JSON literal are tagged with
+//! `string`. Arrays are tagged with `array` when they are empty, consist of a
+//! single reference, or only contain `(id: value)` links, since those would
+//! otherwise read back as a reference or an object. Every JSON value survives
+//! a round trip through `LiNo<String>` and, apart from strings containing both
+//! quote kinds, through Links Notation text.
+//!
+//! At the top level of a text document the members of an object or the
+//! elements of an array are written one per line.
+
+use serde_json::{Map, Number, Value};
+
+use crate::error::{Error, Result};
+use crate::tagging::{
+    array, empty_link, format_document, is_member, parse_document, tagged, tagged_elements, ARRAY_TAG,
+    STRING_TAG,
+};
+use crate::LiNo;
+
+fn is_literal(reference: &str) -> bool {
+    matches!(reference, "null" | "true" | "false") || reference.parse::<Number>().is_ok()
+}
+
+/// Converts a JSON value into a `LiNo<String>` link.
+pub fn json_to_lino(value: &Value) -> LiNo<String> {
+    match value {
+        Value::Null => LiNo::Ref("null".to_string()),
+        Value::Bool(value) => LiNo::Ref(value.to_string()),
+        Value::Number(value) => LiNo::Ref(value.to_string()),
+        Value::String(value) if value.is_empty() => tagged(STRING_TAG, vec![]),
+        Value::String(value) if is_literal(value) => tagged(STRING_TAG, vec![LiNo::Ref(value.clone())]),
+        Value::String(value) => LiNo::Ref(value.clone()),
+        Value::Array(elements) => array(elements.iter().map(json_to_lino).collect()),
+        Value::Object(members) => LiNo::Link {
+            id: None,
+            values: members
+                .iter()
+                .map(|(key, value)| LiNo::Link {
+                    id: Some(key.clone()),
+                    values: vec![json_to_lino(value)],
+                })
+                .collect(),
+        },
+    }
+}
+
+/// Converts a `LiNo<String>` link in the shape produced by [`json_to_lino`]
+/// back into a JSON value.
+pub fn lino_to_json(lino: &LiNo<String>) -> Result<Value> {
+    match lino {
+        LiNo::Ref(reference) => Ok(match reference.as_str() {
+            "null" => Value::Null,
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => match reference.parse::<Number>() {
+                Ok(number) => Value::Number(number),
+                Err(_) => Value::String(reference.clone()),
+            },
+        }),
+        LiNo::Link { id: Some(tag), values } if tag == STRING_TAG => match values.as_slice() {
+            [LiNo::Ref(value)] => Ok(Value::String(value.clone())),
+            [empty] if *empty == empty_link() => Ok(Value::String(String::new())),
+            _ => Err(Error::new("`string` link must contain a single reference or `()`")),
+        },
+        LiNo::Link { id: Some(tag), values } if tag == ARRAY_TAG => tagged_elements(values)
+            .iter()
+            .map(lino_to_json)
+            .collect::<Result<_>>()
+            .map(Value::Array),
+        LiNo::Link { id: Some(id), .. } => Err(Error::new(format!(
+            "unexpected link `{}`: only `string` and `array` links can have an id outside of an object",
+            id
+        ))),
+        LiNo::Link { id: None, values } if values.iter().all(is_member) => {
+            let mut members = Map::new();
+            for member in values {
+                if let LiNo::Link { id: Some(key), values } = member {
+                    members.insert(key.clone(), lino_to_json(&values[0])?);
+                }
+            }
+            Ok(Value::Object(members))
+        }
+        LiNo::Link { id: None, values } => values.iter().map(lino_to_json).collect::<Result<_>>().map(Value::Array),
+    }
+}
+
+/// Writes a JSON value as a Links Notation document.
+pub fn json_to_text(value: &Value) -> Result<String> {
+    format_document(json_to_lino(value))
+}
+
+/// Reads a Links Notation document written by [`json_to_text`] as a JSON value.
+pub fn text_to_json(document: &str) -> Result<Value> {
+    lino_to_json(&parse_document(document)?)
+}
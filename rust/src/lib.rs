@@ -1,6 +1,48 @@
 pub mod parser;
+pub mod analysis;
+pub mod binary;
+pub mod borrowed;
+pub mod codegen;
+pub mod convert;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "serde")]
+pub mod de;
+pub mod diagram;
+mod error;
+pub mod graph;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod pattern;
+pub mod query;
+pub mod rdf;
+pub mod rewrite;
+pub mod schema;
+#[cfg(feature = "serde")]
+pub mod ser;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod sexpr;
+pub mod store;
+#[cfg(any(feature = "json", feature = "yaml", feature = "xml"))]
+mod tagging;
+pub mod visit;
+#[cfg(feature = "xml")]
+pub mod xml;
+#[cfg(feature = "yaml")]
+pub mod yaml;
 
+use std::borrow::Borrow;
 use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub use parser::QuoteStyle;
+pub use convert::{FromLino, ToLino};
+#[cfg(feature = "serde")]
+pub use de::from_str;
+pub use error::Error;
+#[cfg(feature = "serde")]
+pub use ser::{to_lino, to_string, Serializer};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LiNo<T> {
@@ -16,6 +58,228 @@ impl<T> LiNo<T> {
     pub fn is_link(&self) -> bool {
         matches!(self, LiNo::Link { .. })
     }
+
+    /// Id of a link; references have none.
+    pub fn id(&self) -> Option<&T> {
+        match self {
+            LiNo::Link { id, .. } => id.as_ref(),
+            LiNo::Ref(_) => None,
+        }
+    }
+
+    /// Values of a link; references have none.
+    pub fn values(&self) -> &[Self] {
+        match self {
+            LiNo::Link { values, .. } => values,
+            LiNo::Ref(_) => &[],
+        }
+    }
+
+    pub fn values_mut(&mut self) -> &mut [Self] {
+        match self {
+            LiNo::Link { values, .. } => values,
+            LiNo::Ref(_) => &mut [],
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Self> {
+        self.values().get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Self> {
+        self.values_mut().get_mut(index)
+    }
+
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        self.values().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values().is_empty()
+    }
+
+    /// Finds this link or one nested in it, at any depth, with the given id.
+    pub fn find_by_id<Q>(&self, id: &Q) -> Option<&Self>
+    where
+        T: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        match self {
+            LiNo::Link { id: Some(own), .. } if own.borrow() == id => Some(self),
+            LiNo::Link { values, .. } => values.iter().find_map(|value| value.find_by_id(id)),
+            LiNo::Ref(_) => None,
+        }
+    }
+
+    /// Splits into id and values. A reference is returned as an id without
+    /// values, the same way `(name:)` is read as the reference `name`.
+    pub fn into_parts(self) -> (Option<T>, Vec<Self>) {
+        match self {
+            LiNo::Link { id, values } => (id, values),
+            LiNo::Ref(value) => (Some(value), vec![]),
+        }
+    }
+
+    pub fn as_ref(&self) -> LiNo<&T> {
+        match self {
+            LiNo::Link { id, values } => LiNo::Link {
+                id: id.as_ref(),
+                values: values.iter().map(LiNo::as_ref).collect(),
+            },
+            LiNo::Ref(value) => LiNo::Ref(value),
+        }
+    }
+
+    /// Converts every id and reference with `f`.
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> LiNo<U> {
+        self.map_with(&mut f)
+    }
+
+    fn map_with<U, F: FnMut(T) -> U>(self, f: &mut F) -> LiNo<U> {
+        match self {
+            LiNo::Link { id, values } => LiNo::Link {
+                id: id.map(&mut *f),
+                values: values.into_iter().map(|value| value.map_with(f)).collect(),
+            },
+            LiNo::Ref(value) => LiNo::Ref(f(value)),
+        }
+    }
+
+    /// Converts every id and reference with `f`, stopping at the first error.
+    pub fn try_map<U, E, F: FnMut(T) -> Result<U, E>>(self, mut f: F) -> Result<LiNo<U>, E> {
+        self.try_map_with(&mut f)
+    }
+
+    fn try_map_with<U, E, F: FnMut(T) -> Result<U, E>>(self, f: &mut F) -> Result<LiNo<U>, E> {
+        match self {
+            LiNo::Link { id, values } => Ok(LiNo::Link {
+                id: id.map(&mut *f).transpose()?,
+                values: values
+                    .into_iter()
+                    .map(|value| value.try_map_with(f))
+                    .collect::<Result<_, _>>()?,
+            }),
+            LiNo::Ref(value) => f(value).map(LiNo::Ref),
+        }
+    }
+}
+
+impl<T> Index<usize> for LiNo<T> {
+    type Output = LiNo<T>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.values()[index]
+    }
+}
+
+impl<T> IndexMut<usize> for LiNo<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.values_mut()[index]
+    }
+}
+
+impl From<&str> for LiNo<String> {
+    fn from(value: &str) -> Self {
+        LiNo::Ref(value.to_string())
+    }
+}
+
+impl From<String> for LiNo<String> {
+    fn from(value: String) -> Self {
+        LiNo::Ref(value)
+    }
+}
+
+/// A link without id with the given values.
+impl<T> From<Vec<LiNo<T>>> for LiNo<T> {
+    fn from(values: Vec<LiNo<T>>) -> Self {
+        LiNo::Link { id: None, values }
+    }
+}
+
+impl<T: ToString> LiNo<T> {
+    /// Returns the first reference or id that cannot be written in Links Notation:
+    /// an empty one, or one that needs quotes but contains both single and
+    /// double quotes.
+    pub fn find_unrepresentable(&self) -> Option<String> {
+        let check = |value: &T| {
+            let value = value.to_string();
+            (!QuoteStyle::minimal_for(&value).can_represent(&value)).then_some(value)
+        };
+        match self {
+            LiNo::Ref(value) => check(value),
+            LiNo::Link { id, values } => id
+                .as_ref()
+                .and_then(check)
+                .or_else(|| values.iter().find_map(|value| value.find_unrepresentable())),
+        }
+    }
+
+    /// Formats the link as Links Notation, quoting references where needed.
+    ///
+    /// With `less_parentheses` the outer parentheses are omitted where the
+    /// result still parses back to the same link.
+    pub fn format(&self, less_parentheses: bool) -> String {
+        match self {
+            LiNo::Ref(value) => {
+                let value = value.to_string();
+                QuoteStyle::minimal_for(&value).quote(&value)
+            }
+            LiNo::Link { id: None, values } if values.is_empty() => "()".to_string(),
+            LiNo::Link { id: Some(id), values } if values.is_empty() => {
+                let id = id.to_string();
+                let id = QuoteStyle::minimal_for(&id).quote(&id);
+                if less_parentheses {
+                    id
+                } else {
+                    format!("({})", id)
+                }
+            }
+            LiNo::Link { id, values } => {
+                let values_str = values
+                    .iter()
+                    .map(|value| value.format(false))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let content = match id {
+                    Some(id) => {
+                        let id = id.to_string();
+                        format!("{}: {}", QuoteStyle::minimal_for(&id).quote(&id), values_str)
+                    }
+                    // A single value without parentheses would be read back as a reference
+                    None if values.len() == 1 => return format!("({})", values_str),
+                    None => values_str,
+                };
+                if less_parentheses {
+                    content
+                } else {
+                    format!("({})", content)
+                }
+            }
+        }
+    }
+}
+
+// Fails on the first reference or id of `link` that cannot be written in
+// Links Notation, for conversions that produce Links Notation text
+pub(crate) fn check_representable<T: ToString>(link: &LiNo<T>) -> Result<(), Error> {
+    match link.find_unrepresentable() {
+        Some(reference) => Err(Error::new(format!(
+            "reference {:?} cannot be written in Links Notation",
+            reference
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Formats a collection of links, one link per line.
+pub fn format_links<T: ToString>(links: &[LiNo<T>], less_parentheses: bool) -> String {
+    links
+        .iter()
+        .map(|link| link.format(less_parentheses))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 impl<T: ToString> fmt::Display for LiNo<T> {
@@ -73,11 +337,11 @@ impl From<parser::Link> for LiNo<String> {
 }
 
 // Helper function to flatten indented structures according to Lino spec
-fn flatten_links(links: Vec<parser::Link>) -> Vec<LiNo<String>> {
+pub(crate) fn flatten_links(links: &[parser::Link]) -> Vec<LiNo<String>> {
     let mut result = vec![];
     
     for link in links {
-        flatten_link_recursive(&link, None, &mut result);
+        flatten_link_recursive(link, None, &mut result);
     }
     
     result
@@ -97,6 +361,8 @@ fn flatten_link_recursive(link: &parser::Link, parent: Option<LiNo<String>>, res
                     values: child.values.clone(),
                     children: vec![],
                     is_indented_id: false,
+                    quote_style: child.quote_style,
+                    offset: child.offset,
                 }.into()
             }
         }).collect();
@@ -139,6 +405,8 @@ fn flatten_link_recursive(link: &parser::Link, parent: Option<LiNo<String>>, res
                 values: v.values.clone(),
                 children: vec![],
                 is_indented_id: false,
+                quote_style: v.quote_style,
+                offset: v.offset,
             }.into()
         }).collect();
         LiNo::Link { id: link.id.clone(), values }
@@ -186,7 +454,7 @@ pub fn parse_lino(document: &str) -> Result<LiNo<String>, String> {
                 Ok(LiNo::Link { id: None, values: vec![] })
             } else {
                 // Flatten the indented structure according to Lino spec
-                let flattened = flatten_links(links);
+                let flattened = flatten_links(&links);
                 Ok(LiNo::Link { id: None, values: flattened })
             }
         }
@@ -207,7 +475,7 @@ pub fn parse_lino_to_links(document: &str) -> Result<Vec<LiNo<String>>, String>
                 Ok(vec![])
             } else {
                 // Flatten the indented structure according to Lino spec
-                let flattened = flatten_links(links);
+                let flattened = flatten_links(&links);
                 Ok(flattened)
             }
         }
@@ -215,3 +483,15 @@ pub fn parse_lino_to_links(document: &str) -> Result<Vec<LiNo<String>>, String>
     }
 }
 
+/// Returns the parser's syntax tree, which keeps indentation and the quote
+/// style of every reference.
+pub fn parse_lino_to_ast(document: &str) -> Result<Vec<parser::Link>, String> {
+    if document.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    match parser::parse_document(document) {
+        Ok((_, links)) => Ok(links),
+        Err(e) => Err(format!("Parse error: {:?}", e))
+    }
+}
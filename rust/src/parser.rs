@@ -9,6 +9,65 @@ use nom::{
     Parser,
 };
 use std::cell::RefCell;
+use std::fmt;
+
+/// How a reference was written in the source document.
+///
+/// Multi-line references are always quoted, so they are reported as
+/// `SingleQuoted` or `DoubleQuoted` with a line break in the reference itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QuoteStyle {
+    #[default]
+    Bare,
+    SingleQuoted,
+    DoubleQuoted,
+}
+
+impl QuoteStyle {
+    /// Returns the simplest style that can represent `reference` unambiguously.
+    pub fn minimal_for(reference: &str) -> Self {
+        if QuoteStyle::Bare.can_represent(reference) {
+            QuoteStyle::Bare
+        } else if !reference.contains('\'') {
+            QuoteStyle::SingleQuoted
+        } else {
+            QuoteStyle::DoubleQuoted
+        }
+    }
+
+    /// Returns true if `reference` can be written with this style and parsed back unchanged.
+    pub fn can_represent(&self, reference: &str) -> bool {
+        match self {
+            // A leading quote would start a quoted reference instead
+            QuoteStyle::Bare => {
+                !reference.is_empty()
+                    && !reference.starts_with(['\'', '"'])
+                    && reference.chars().all(is_reference_char)
+            }
+            QuoteStyle::SingleQuoted => !reference.is_empty() && !reference.contains('\''),
+            QuoteStyle::DoubleQuoted => !reference.is_empty() && !reference.contains('"'),
+        }
+    }
+
+    pub fn is_quoted(&self) -> bool {
+        !matches!(self, QuoteStyle::Bare)
+    }
+
+    /// Writes `reference` using this style, falling back to the minimal style
+    /// when this one cannot represent it.
+    pub fn quote(&self, reference: &str) -> String {
+        let style = if self.can_represent(reference) {
+            *self
+        } else {
+            Self::minimal_for(reference)
+        };
+        match style {
+            QuoteStyle::Bare => reference.to_string(),
+            QuoteStyle::SingleQuoted => format!("'{}'", reference),
+            QuoteStyle::DoubleQuoted => format!("\"{}\"", reference),
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Link {
@@ -16,6 +75,9 @@ pub struct Link {
     pub values: Vec<Link>,
     pub children: Vec<Link>,
     pub is_indented_id: bool,
+    pub quote_style: QuoteStyle,
+    /// Byte offset of the link in the parsed document.
+    pub offset: usize,
 }
 
 impl Link {
@@ -25,6 +87,8 @@ impl Link {
             values: vec![],
             children: vec![],
             is_indented_id: false,
+            quote_style: QuoteStyle::Bare,
+            offset: 0,
         }
     }
 
@@ -34,6 +98,8 @@ impl Link {
             values: vec![],
             children: vec![],
             is_indented_id: true,
+            quote_style: QuoteStyle::Bare,
+            offset: 0,
         }
     }
 
@@ -43,6 +109,8 @@ impl Link {
             values,
             children: vec![],
             is_indented_id: false,
+            quote_style: QuoteStyle::Bare,
+            offset: 0,
         }
     }
 
@@ -52,6 +120,8 @@ impl Link {
             values,
             children: vec![],
             is_indented_id: false,
+            quote_style: QuoteStyle::Bare,
+            offset: 0,
         }
     }
 
@@ -59,19 +129,112 @@ impl Link {
         self.children = children;
         self
     }
+
+    pub fn with_quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    fn id_string(&self) -> Option<String> {
+        self.id.as_ref().map(|id| self.quote_style.quote(id))
+    }
+
+    fn fmt_inline(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.values.is_empty() {
+            if let Some(id) = self.id_string() {
+                return write!(f, "{}", id);
+            }
+        }
+        write!(f, "(")?;
+        if let Some(id) = self.id_string() {
+            write!(f, "{}:", id)?;
+            if !self.values.is_empty() {
+                write!(f, " ")?;
+            }
+        }
+        for (index, value) in self.values.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            value.fmt_inline(f)?;
+        }
+        write!(f, ")")
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        write!(f, "{:indent$}", "", indent = indent)?;
+        match (&self.id_string(), self.is_indented_id && self.values.is_empty()) {
+            (Some(id), true) => write!(f, "{}:", id)?,
+            _ => self.fmt_inline(f)?,
+        }
+        for child in &self.children {
+            writeln!(f)?;
+            child.fmt_indented(f, indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+// Formats the link back into Links Notation, keeping the original quote style
+// of every reference and the indentation of children.
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// Converts a byte offset in `document` into a 1-based line and column.
+pub fn line_column(document: &str, offset: usize) -> (usize, usize) {
+    let before = &document[..offset.min(document.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+/// Formats a parsed document, one top-level link per line.
+pub fn format_document(links: &[Link]) -> String {
+    links
+        .iter()
+        .map(|link| link.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 pub struct ParserState {
     indentation_stack: RefCell<Vec<usize>>,
+    document_len: usize,
+}
+
+impl Default for ParserState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ParserState {
     pub fn new() -> Self {
         ParserState {
             indentation_stack: RefCell::new(vec![0]),
+            document_len: 0,
+        }
+    }
+
+    pub fn for_document(document: &str) -> Self {
+        ParserState {
+            indentation_stack: RefCell::new(vec![0]),
+            document_len: document.len(),
         }
     }
 
+    pub fn offset_of(&self, input: &str) -> usize {
+        self.document_len.saturating_sub(input.len())
+    }
+
     pub fn push_indentation(&self, indent: usize) {
         self.indentation_stack.borrow_mut().push(indent);
     }
@@ -112,33 +275,33 @@ fn whitespace(input: &str) -> IResult<&str, &str> {
     take_while(is_whitespace_char)(input)
 }
 
-fn simple_reference(input: &str) -> IResult<&str, String> {
+fn simple_reference(input: &str) -> IResult<&str, (String, QuoteStyle)> {
     take_while1(is_reference_char)
-        .map(|s: &str| s.to_string())
+        .map(|s: &str| (s.to_string(), QuoteStyle::Bare))
         .parse(input)
 }
 
-fn double_quoted_reference(input: &str) -> IResult<&str, String> {
+fn double_quoted_reference(input: &str) -> IResult<&str, (String, QuoteStyle)> {
     delimited(
         char('"'),
         is_not("\""),
         char('"')
     )
-    .map(|s: &str| s.to_string())
+    .map(|s: &str| (s.to_string(), QuoteStyle::DoubleQuoted))
     .parse(input)
 }
 
-fn single_quoted_reference(input: &str) -> IResult<&str, String> {
+fn single_quoted_reference(input: &str) -> IResult<&str, (String, QuoteStyle)> {
     delimited(
         char('\''),
         is_not("'"),
         char('\'')
     )
-    .map(|s: &str| s.to_string())
+    .map(|s: &str| (s.to_string(), QuoteStyle::SingleQuoted))
     .parse(input)
 }
 
-fn reference(input: &str) -> IResult<&str, String> {
+fn reference(input: &str) -> IResult<&str, (String, QuoteStyle)> {
     alt((
         double_quoted_reference,
         single_quoted_reference,
@@ -156,10 +319,13 @@ fn eol(input: &str) -> IResult<&str, &str> {
 
 
 fn reference_or_link<'a>(input: &'a str, state: &ParserState) -> IResult<&'a str, Link> {
+    let offset = state.offset_of(input);
     alt((
         |i| multi_line_any_link(i, state),
-        reference.map(Link::new_singlet),
-    )).parse(input)
+        reference.map(|(id, quote_style)| Link::new_singlet(id).with_quote_style(quote_style)),
+    ))
+    .map(|link| link.with_offset(offset))
+    .parse(input)
 }
 
 fn multi_line_value_and_whitespace<'a>(input: &'a str, state: &ParserState) -> IResult<&'a str, Link> {
@@ -194,7 +360,7 @@ fn single_line_link<'a>(input: &'a str, state: &ParserState) -> IResult<&'a str,
         horizontal_whitespace,
         char(':'),
         |i| single_line_values(i, state)
-    ).map(|(_, id, _, _, values)| Link::new_link(Some(id), values))
+    ).map(|(_, (id, quote_style), _, _, values)| Link::new_link(Some(id), values).with_quote_style(quote_style))
     .parse(input)
 }
 
@@ -208,7 +374,7 @@ fn multi_line_link<'a>(input: &'a str, state: &ParserState) -> IResult<&'a str,
         |i| multi_line_values(i, state),
         whitespace,
         char(')')
-    ).map(|(_, _, id, _, _, values, _, _)| Link::new_link(Some(id), values))
+    ).map(|(_, _, (id, quote_style), _, _, values, _, _)| Link::new_link(Some(id), values).with_quote_style(quote_style))
     .parse(input)
 }
 
@@ -216,7 +382,7 @@ fn single_line_value_link<'a>(input: &'a str, state: &ParserState) -> IResult<&'
     (|i| single_line_values(i, state))
         .map(|values| {
             if values.len() == 1 && values[0].id.is_some() && values[0].values.is_empty() && values[0].children.is_empty() {
-                Link::new_singlet(values[0].id.clone().unwrap())
+                Link::new_singlet(values[0].id.clone().unwrap()).with_quote_style(values[0].quote_style)
             } else {
                 Link::new_value(values)
             }
@@ -230,7 +396,7 @@ fn indented_id_link<'a>(input: &'a str, _state: &ParserState) -> IResult<&'a str
         horizontal_whitespace,
         char(':'),
         eol
-    ).map(|(id, _, _, _)| Link::new_indented_id(id))
+    ).map(|((id, quote_style), _, _, _)| Link::new_indented_id(id).with_quote_style(quote_style))
     .parse(input)
 }
 
@@ -242,7 +408,7 @@ fn multi_line_value_link<'a>(input: &'a str, state: &ParserState) -> IResult<&'a
         char(')')
     ).map(|(_, values, _, _)| {
         if values.len() == 1 && values[0].id.is_some() && values[0].values.is_empty() && values[0].children.is_empty() {
-            Link::new_singlet(values[0].id.clone().unwrap())
+            Link::new_singlet(values[0].id.clone().unwrap()).with_quote_style(values[0].quote_style)
         } else {
             Link::new_value(values)
         }
@@ -265,11 +431,14 @@ fn single_line_any_link<'a>(input: &'a str, state: &ParserState) -> IResult<&'a
 }
 
 fn any_link<'a>(input: &'a str, state: &ParserState) -> IResult<&'a str, Link> {
+    let offset = state.offset_of(input.trim_start_matches([' ', '\t']));
     alt((
         terminated(|i| multi_line_any_link(i, state), eol),
         |i| indented_id_link(i, state),
         |i| single_line_any_link(i, state),
-    )).parse(input)
+    ))
+    .map(|link| link.with_offset(offset))
+    .parse(input)
 }
 
 fn count_indentation(input: &str) -> IResult<&str, usize> {
@@ -334,10 +503,10 @@ fn links<'a>(input: &'a str, state: &ParserState) -> IResult<&'a str, Vec<Link>>
 }
 
 pub fn parse_document(input: &str) -> IResult<&str, Vec<Link>> {
-    let state = ParserState::new();
+    let state = ParserState::for_document(input);
     
     // Skip leading whitespace but preserve the line structure
-    let input = input.trim_start_matches(|c: char| c == '\n' || c == '\r');
+    let input = input.trim_start_matches(['\n', '\r']);
     
     // Handle empty or whitespace-only documents
     if input.trim().is_empty() {
@@ -0,0 +1,291 @@
+//! Structural pattern matching with variables.
+//!
+//! Patterns are links written in Links Notation, in which references starting
+//! with `$` are variables:
+//!
+//! - `$x` matches any single value, or an id when used as one, and binds it;
+//!   a variable used more than once must match equal values each time;
+//! - `$_` matches any single value without binding it;
+//! - `$xs..` among the values of a link matches any number of values and
+//!   binds them as a sequence.
+//!
+//! Other references only match equal references, and links match links with
+//! matching values. A pattern without an id matches links with any id, so
+//! `($y knows $z)` matches `(fact: alice knows bob)` and
+//! `($x: $y knows $z)` also binds the id to `$x`.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Error, Result};
+use crate::{parse_lino_to_links, LiNo};
+
+const WILDCARD: &str = "$_";
+
+/// What a variable is bound to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Binding {
+    /// A single value, or an id bound as a reference.
+    Value(LiNo<String>),
+    /// The values matched by a sequence variable.
+    Values(Vec<LiNo<String>>),
+}
+
+impl Binding {
+    pub fn as_value(&self) -> Option<&LiNo<String>> {
+        match self {
+            Binding::Value(value) => Some(value),
+            Binding::Values(_) => None,
+        }
+    }
+
+    pub fn as_values(&self) -> Option<&[LiNo<String>]> {
+        match self {
+            Binding::Value(_) => None,
+            Binding::Values(values) => Some(values),
+        }
+    }
+}
+
+/// Variables bound by a match, by name without the `$`.
+pub type Bindings = BTreeMap<String, Binding>;
+
+/// A link matched by a pattern, with the bindings of the match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match<'a> {
+    pub link: &'a LiNo<String>,
+    pub bindings: Bindings,
+}
+
+enum Variable<'a> {
+    Single(&'a str),
+    Sequence(&'a str),
+}
+
+fn variable(name: &str) -> Option<Variable<'_>> {
+    let name = name.strip_prefix('$').filter(|name| !name.is_empty())?;
+    Some(match name.strip_suffix("..") {
+        Some("") => return None,
+        Some(name) => Variable::Sequence(name),
+        None => Variable::Single(name),
+    })
+}
+
+fn bind(bindings: &Bindings, name: &str, binding: Binding) -> Option<Bindings> {
+    if name == &WILDCARD[1..] {
+        return Some(bindings.clone());
+    }
+    match bindings.get(name) {
+        Some(bound) => (*bound == binding).then(|| bindings.clone()),
+        None => {
+            let mut bindings = bindings.clone();
+            bindings.insert(name.to_string(), binding);
+            Some(bindings)
+        }
+    }
+}
+
+fn match_id(pattern: &Option<String>, id: &Option<String>, bindings: &Bindings) -> Option<Bindings> {
+    match (pattern, id) {
+        (None, _) => Some(bindings.clone()),
+        (Some(pattern), Some(id)) => match variable(pattern) {
+            Some(Variable::Single(name)) => bind(bindings, name, Binding::Value(LiNo::Ref(id.clone()))),
+            Some(Variable::Sequence(_)) => None,
+            None => (pattern == id).then(|| bindings.clone()),
+        },
+        (Some(_), None) => None,
+    }
+}
+
+// The matchers pass each way of matching to `rest`, which checks the rest of
+// the pattern, so a choice made inside a nested link can be revisited when a
+// later part of the pattern fails.
+type Rest<'r> = &'r mut dyn FnMut(Bindings) -> bool;
+
+fn match_value(pattern: &LiNo<String>, value: &LiNo<String>, bindings: &Bindings, rest: Rest) -> bool {
+    match (pattern, value) {
+        (LiNo::Ref(pattern), value) if pattern.starts_with('$') => match variable(pattern) {
+            Some(Variable::Single(name)) => bind(bindings, name, Binding::Value(value.clone())).is_some_and(rest),
+            Some(Variable::Sequence(_)) => false,
+            None => matches!(value, LiNo::Ref(value) if value == pattern) && rest(bindings.clone()),
+        },
+        (LiNo::Ref(pattern), LiNo::Ref(value)) => pattern == value && rest(bindings.clone()),
+        (LiNo::Link { id: pattern_id, values: patterns }, LiNo::Link { id, values }) => {
+            match match_id(pattern_id, id, bindings) {
+                Some(bindings) => match_values(patterns, values, &bindings, rest),
+                None => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn match_values(patterns: &[LiNo<String>], values: &[LiNo<String>], bindings: &Bindings, rest: Rest) -> bool {
+    let Some((pattern, patterns)) = patterns.split_first() else {
+        return values.is_empty() && rest(bindings.clone());
+    };
+    if let LiNo::Ref(name) = pattern {
+        if let Some(Variable::Sequence(name)) = variable(name) {
+            // Shortest sequences first, so matches are deterministic
+            return (0..=values.len()).any(|len| {
+                bind(bindings, name, Binding::Values(values[..len].to_vec()))
+                    .is_some_and(|bindings| match_values(patterns, &values[len..], &bindings, rest))
+            });
+        }
+    }
+    let Some((value, values)) = values.split_first() else {
+        return false;
+    };
+    match_value(pattern, value, bindings, &mut |bindings| {
+        match_values(patterns, values, &bindings, rest)
+    })
+}
+
+fn collect_variables<'a>(lino: &'a LiNo<String>, names: &mut Vec<&'a str>) {
+    let mut note = |name: &'a str| {
+        if let Some(Variable::Single(name) | Variable::Sequence(name)) = variable(name) {
+            if name != &WILDCARD[1..] && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    };
+    match lino {
+        LiNo::Ref(name) => note(name),
+        LiNo::Link { id, values } => {
+            if let Some(id) = id {
+                note(id);
+            }
+            for value in values {
+                collect_variables(value, names);
+            }
+        }
+    }
+}
+
+/// Names of the variables used in `lino`, in order of appearance.
+pub(crate) fn variables(lino: &LiNo<String>) -> Vec<&str> {
+    let mut names = vec![];
+    collect_variables(lino, &mut names);
+    names
+}
+
+fn substitute_values(templates: &[LiNo<String>], bindings: &Bindings) -> Result<Vec<LiNo<String>>> {
+    let mut values = Vec::with_capacity(templates.len());
+    for template in templates {
+        match template {
+            LiNo::Ref(name) if matches!(variable(name), Some(Variable::Sequence(_))) => {
+                let name = &name[1..name.len() - 2];
+                match bindings.get(name) {
+                    Some(Binding::Values(bound)) => values.extend(bound.iter().cloned()),
+                    Some(Binding::Value(_)) => return Err(Error::new(format!("`${}` is not a sequence", name))),
+                    None => return Err(Error::new(format!("unbound variable `${}`", name))),
+                }
+            }
+            template => values.push(substitute(template, bindings)?),
+        }
+    }
+    Ok(values)
+}
+
+fn substitute_id(id: &str, bindings: &Bindings) -> Result<String> {
+    match variable(id) {
+        None => Ok(id.to_string()),
+        Some(Variable::Single(name)) => match bindings.get(name) {
+            Some(Binding::Value(LiNo::Ref(bound))) => Ok(bound.clone()),
+            Some(_) => Err(Error::new(format!("`${}` is not bound to a reference, so it cannot be an id", name))),
+            None => Err(Error::new(format!("unbound variable `${}`", name))),
+        },
+        Some(Variable::Sequence(name)) => Err(Error::new(format!("`${}..` cannot be an id", name))),
+    }
+}
+
+/// Replaces the variables of `template` with their bindings. Sequence
+/// variables are spliced into the values of their link.
+pub fn substitute(template: &LiNo<String>, bindings: &Bindings) -> Result<LiNo<String>> {
+    match template {
+        LiNo::Ref(name) => match variable(name) {
+            None => Ok(template.clone()),
+            Some(Variable::Single(name)) => match bindings.get(name) {
+                Some(Binding::Value(bound)) => Ok(bound.clone()),
+                Some(Binding::Values(_)) => Err(Error::new(format!("`${}` is a sequence", name))),
+                None => Err(Error::new(format!("unbound variable `${}`", name))),
+            },
+            Some(Variable::Sequence(name)) => Err(Error::new(format!("`${}..` can only be used among values", name))),
+        },
+        LiNo::Link { id, values } => Ok(LiNo::Link {
+            id: id.as_deref().map(|id| substitute_id(id, bindings)).transpose()?,
+            values: substitute_values(values, bindings)?,
+        }),
+    }
+}
+
+fn visit<'a>(lino: &'a LiNo<String>, found: &mut dyn FnMut(&'a LiNo<String>)) {
+    found(lino);
+    if let LiNo::Link { values, .. } = lino {
+        for value in values {
+            visit(value, found);
+        }
+    }
+}
+
+/// A pattern with variables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    lino: LiNo<String>,
+}
+
+impl Pattern {
+    pub fn new(lino: LiNo<String>) -> Self {
+        Pattern { lino }
+    }
+
+    /// Parses a pattern written as a single link.
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let mut links = parse_lino_to_links(pattern).map_err(Error::new)?;
+        match links.len() {
+            1 => {
+                let lino = links.remove(0);
+                if lino.pre_order().any(|node| match node.lino {
+                    LiNo::Ref(name) => name == "$..",
+                    LiNo::Link { id, .. } => id.as_deref() == Some("$.."),
+                }) {
+                    return Err(Error::new("`$..` is missing a variable name"));
+                }
+                Ok(Pattern::new(lino))
+            }
+            count => Err(Error::new(format!("expected a single link, found {}", count))),
+        }
+    }
+
+    pub fn lino(&self) -> &LiNo<String> {
+        &self.lino
+    }
+
+    /// Names of the variables of the pattern, in order of appearance.
+    pub fn variables(&self) -> Vec<&str> {
+        variables(&self.lino)
+    }
+
+    /// Matches `value` itself, returning the bindings if it matches.
+    pub fn matches(&self, value: &LiNo<String>) -> Option<Bindings> {
+        let mut found = None;
+        match_value(&self.lino, value, &Bindings::new(), &mut |bindings| {
+            found = Some(bindings);
+            true
+        });
+        found
+    }
+
+    /// Every link or reference matching the pattern, at any depth, in
+    /// document order.
+    pub fn find_all<'a>(&self, links: &'a [LiNo<String>]) -> Vec<Match<'a>> {
+        let mut matches = vec![];
+        for link in links {
+            visit(link, &mut |value| {
+                if let Some(bindings) = self.matches(value) {
+                    matches.push(Match { link: value, bindings });
+                }
+            });
+        }
+        matches
+    }
+}
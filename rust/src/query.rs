@@ -0,0 +1,453 @@
+//! LinoPath, a query language for selecting links.
+//!
+//! A query is a path of steps, each selecting values of the links selected by
+//! the previous step:
+//!
+//! | Syntax          | Selects                                                    |
+//! |-----------------|------------------------------------------------------------|
+//! | `/step`         | values of the current links that match `step`              |
+//! | `//step`        | values at any depth below the current links                |
+//! | `*`             | any link or reference                                      |
+//! | `name`, `'a b'` | links with that id and references with that name           |
+//! | `(a * ..)`      | links whose values match: `*` is any value, `..` any number of values, and nested patterns match nested links |
+//! | `(id: ..)`      | the same, for links with id `id`; `(*: ..)` requires any id |
+//! | `->`            | the links named by the current references; `//->` also follows the references below them |
+//! | `step[2]`       | the second value of its link; `[-1]` is the last one       |
+//! | `step[depth=2]` | values at a depth of 2; `<` and `>` compare too            |
+//!
+//! Queries start from the document, so `/papa` selects the top-level link
+//! `(papa: loves mama)` and `/papa/*[2]/->` the link that defines `mama`.
+//! Top-level links have a depth of 1 and their values a depth of 2. Results
+//! are in document order, each link at most once. References followed with
+//! `->` resolve to the first link defined with that id anywhere in the
+//! document.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::LiNo;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    Child,
+    Descendant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    Any,
+    Rest,
+    Name(String),
+    Pattern(Pattern),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Pattern {
+    // `Some(None)` for `*:`, which matches any id
+    id: Option<Option<String>>,
+    values: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Test {
+    Any,
+    Name(String),
+    Pattern(Pattern),
+    Follow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    Position(isize),
+    Depth(Ordering, usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    axis: Axis,
+    test: Test,
+    predicates: Vec<Predicate>,
+}
+
+/// A parsed LinoPath query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    steps: Vec<Step>,
+}
+
+fn name_of<T: AsRef<str>>(lino: &LiNo<T>) -> Option<&str> {
+    match lino {
+        LiNo::Ref(name) => Some(name.as_ref()),
+        LiNo::Link { id, .. } => id.as_ref().map(AsRef::as_ref),
+    }
+}
+
+fn matches_item<T: AsRef<str>>(item: &Item, lino: &LiNo<T>) -> bool {
+    match item {
+        Item::Any | Item::Rest => true,
+        Item::Name(name) => name_of(lino) == Some(name.as_str()),
+        Item::Pattern(pattern) => matches_pattern(pattern, lino),
+    }
+}
+
+fn matches_pattern<T: AsRef<str>>(pattern: &Pattern, lino: &LiNo<T>) -> bool {
+    let LiNo::Link { id, values } = lino else {
+        return false;
+    };
+    let id_matches = match (&pattern.id, id) {
+        (None, _) => true,
+        (Some(Some(expected)), Some(id)) => expected == id.as_ref(),
+        (Some(expected), id) => expected.is_none() && id.is_some(),
+    };
+    id_matches && matches_values(&pattern.values, values)
+}
+
+fn matches_values<T: AsRef<str>>(items: &[Item], values: &[LiNo<T>]) -> bool {
+    match items.split_first() {
+        None => values.is_empty(),
+        Some((Item::Rest, rest)) => (0..=values.len()).any(|skip| matches_values(rest, &values[skip..])),
+        Some((item, rest)) => values
+            .split_first()
+            .is_some_and(|(value, values)| matches_item(item, value) && matches_values(rest, values)),
+    }
+}
+
+struct Entry<'a, T> {
+    lino: Option<&'a LiNo<T>>,
+    depth: usize,
+    position: usize,
+    siblings: usize,
+    children: Vec<usize>,
+    // Index past the last descendant, as entries are in document order
+    end: usize,
+}
+
+struct Tree<'a, T> {
+    entries: Vec<Entry<'a, T>>,
+    definitions: HashMap<&'a str, usize>,
+}
+
+impl<'a, T: AsRef<str>> Tree<'a, T> {
+    fn new(root: Option<&'a LiNo<T>>, links: &'a [LiNo<T>]) -> Self {
+        let mut tree = Tree {
+            entries: vec![],
+            definitions: HashMap::new(),
+        };
+        tree.entries.push(Entry {
+            lino: root,
+            depth: 0,
+            position: 1,
+            siblings: 1,
+            children: vec![],
+            end: 0,
+        });
+        if let Some(LiNo::Link { id: Some(id), .. }) = root {
+            tree.definitions.insert(id.as_ref(), 0);
+        }
+        tree.entries[0].children = tree.add_values(links, 1);
+        tree.entries[0].end = tree.entries.len();
+        tree
+    }
+
+    fn add_values(&mut self, values: &'a [LiNo<T>], depth: usize) -> Vec<usize> {
+        let mut children = Vec::with_capacity(values.len());
+        for (index, value) in values.iter().enumerate() {
+            let entry = self.entries.len();
+            children.push(entry);
+            self.entries.push(Entry {
+                lino: Some(value),
+                depth,
+                position: index + 1,
+                siblings: values.len(),
+                children: vec![],
+                end: 0,
+            });
+            if let LiNo::Link { id, values } = value {
+                if let Some(id) = id {
+                    self.definitions.entry(id.as_ref()).or_insert(entry);
+                }
+                self.entries[entry].children = self.add_values(values, depth + 1);
+            }
+            self.entries[entry].end = self.entries.len();
+        }
+        children
+    }
+
+    fn test(&self, test: &Test, index: usize) -> Option<usize> {
+        let lino = self.entries[index].lino?;
+        match test {
+            Test::Any => Some(index),
+            Test::Name(name) => (name_of(lino) == Some(name.as_str())).then_some(index),
+            Test::Pattern(pattern) => matches_pattern(pattern, lino).then_some(index),
+            Test::Follow => match lino {
+                LiNo::Ref(name) => self.definitions.get(name.as_ref()).copied(),
+                LiNo::Link { .. } => None,
+            },
+        }
+    }
+
+    fn holds(&self, predicate: &Predicate, index: usize) -> bool {
+        let entry = &self.entries[index];
+        match predicate {
+            Predicate::Position(position) if *position < 0 => entry.siblings as isize + 1 + position == entry.position as isize,
+            Predicate::Position(position) => entry.position as isize == *position,
+            Predicate::Depth(ordering, depth) => entry.depth.cmp(depth) == *ordering,
+        }
+    }
+
+    fn select(&self, steps: &[Step]) -> Vec<&'a LiNo<T>> {
+        let mut current = vec![0];
+        for step in steps {
+            let mut selected = vec![];
+            for &index in &current {
+                // `->` follows the current references themselves, or with `//`
+                // also the references below them
+                let first = if step.test == Test::Follow { index } else { index + 1 };
+                let candidates = match step.axis {
+                    Axis::Child if step.test == Test::Follow => vec![index],
+                    Axis::Child => self.entries[index].children.clone(),
+                    Axis::Descendant => (first..self.entries[index].end).collect(),
+                };
+                for candidate in candidates {
+                    if let Some(target) = self.test(&step.test, candidate) {
+                        if step.predicates.iter().all(|predicate| self.holds(predicate, target)) {
+                            selected.push(target);
+                        }
+                    }
+                }
+            }
+            selected.sort_unstable();
+            selected.dedup();
+            current = selected;
+        }
+        current.into_iter().filter_map(|index| self.entries[index].lino).collect()
+    }
+}
+
+struct Reader<'a> {
+    query: &'a str,
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn error(&self, message: impl Into<String>, offset: usize) -> Error {
+        Error::new(message).at(self.query, offset)
+    }
+
+    fn rest(&self) -> &str {
+        &self.query[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.position += token.len();
+        }
+        found
+    }
+
+    // Like `eat`, but only if `token` is not the start of a longer name
+    fn eat_word(&mut self, token: &str) -> bool {
+        let delimited = self.rest().strip_prefix(token).is_some_and(|after| after.chars().next().is_none_or(is_delimiter));
+        delimited && self.eat(token)
+    }
+
+    fn step(&mut self, axis: Axis) -> Result<Step> {
+        let start = self.position;
+        let test = if self.eat_word("->") {
+            Test::Follow
+        } else if self.eat_word("*") {
+            Test::Any
+        } else if self.eat("(") {
+            Test::Pattern(self.pattern(start)?)
+        } else if self.peek().is_some_and(|c| !is_delimiter(c) || c == '\'' || c == '"') {
+            Test::Name(self.name()?)
+        } else {
+            return Err(self.error("expected a step", start));
+        };
+        let mut predicates = vec![];
+        loop {
+            self.skip_whitespace();
+            if !self.eat("[") {
+                break;
+            }
+            predicates.push(self.predicate()?);
+        }
+        Ok(Step { axis, test, predicates })
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let start = self.position;
+        let rest = self.rest();
+        let (name, len) = match self.peek() {
+            Some(quote @ ('\'' | '"')) => match rest[1..].find(quote) {
+                Some(end) => (rest[1..end + 1].to_string(), end + 2),
+                None => return Err(self.error("unterminated name", start)),
+            },
+            _ => {
+                let end = rest.find(is_delimiter).unwrap_or(rest.len());
+                if end == 0 {
+                    return Err(self.error("expected a name", start));
+                }
+                (rest[..end].to_string(), end)
+            }
+        };
+        self.position += len;
+        Ok(name)
+    }
+
+    fn item(&mut self) -> Result<Item> {
+        let start = self.position;
+        if self.eat_word("*") {
+            Ok(Item::Any)
+        } else if self.eat_word("..") {
+            Ok(Item::Rest)
+        } else if self.eat("(") {
+            self.pattern(start).map(Item::Pattern)
+        } else {
+            self.name().map(Item::Name)
+        }
+    }
+
+    fn pattern(&mut self, start: usize) -> Result<Pattern> {
+        let mut pattern = Pattern { id: None, values: vec![] };
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(')') => {
+                    self.position += 1;
+                    return Ok(pattern);
+                }
+                None => return Err(self.error("unterminated pattern", start)),
+                Some(_) => {
+                    let item_start = self.position;
+                    let item = self.item()?;
+                    self.skip_whitespace();
+                    if self.peek() != Some(':') {
+                        pattern.values.push(item);
+                    } else if pattern.id.is_none() && pattern.values.is_empty() {
+                        pattern.id = match item {
+                            Item::Any => Some(None),
+                            Item::Name(name) => Some(Some(name)),
+                            _ => return Err(self.error("unexpected `:`", item_start)),
+                        };
+                        self.position += 1;
+                    } else {
+                        return Err(self.error("unexpected `:`", item_start));
+                    }
+                }
+            }
+        }
+    }
+
+    fn predicate(&mut self) -> Result<Predicate> {
+        self.skip_whitespace();
+        let predicate = if self.eat("depth") {
+            self.skip_whitespace();
+            let ordering = match self.peek() {
+                Some('<') => Ordering::Less,
+                Some('=') => Ordering::Equal,
+                Some('>') => Ordering::Greater,
+                _ => return Err(self.error("expected `<`, `=` or `>`", self.position)),
+            };
+            self.position += 1;
+            self.skip_whitespace();
+            let start = self.position;
+            match usize::try_from(self.number()?) {
+                Ok(depth) => Predicate::Depth(ordering, depth),
+                Err(_) => return Err(self.error("depths cannot be negative", start)),
+            }
+        } else {
+            let start = self.position;
+            match self.number()? {
+                0 => return Err(self.error("positions start at 1", start)),
+                position => Predicate::Position(position),
+            }
+        };
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            return Err(self.error("expected `]`", self.position));
+        }
+        self.position += 1;
+        Ok(predicate)
+    }
+
+    fn number(&mut self) -> Result<isize> {
+        let start = self.position;
+        let rest = self.rest();
+        let digits = rest.strip_prefix('-').unwrap_or(rest);
+        let end = rest.len() - digits.len() + digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+        let number = rest[..end].parse().map_err(|_| self.error("expected a number", start))?;
+        self.position += end;
+        Ok(number)
+    }
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || "/[]()'\":".contains(c)
+}
+
+impl Query {
+    /// Parses a LinoPath query.
+    pub fn parse(query: &str) -> Result<Self> {
+        let mut reader = Reader { query, position: 0 };
+        let mut steps = vec![];
+        reader.skip_whitespace();
+        let mut axis = if reader.eat("//") {
+            Axis::Descendant
+        } else {
+            reader.eat("/");
+            Axis::Child
+        };
+        loop {
+            reader.skip_whitespace();
+            steps.push(reader.step(axis)?);
+            reader.skip_whitespace();
+            axis = if reader.peek().is_none() {
+                break;
+            } else if reader.eat("//") {
+                Axis::Descendant
+            } else if reader.eat("/") {
+                Axis::Child
+            } else {
+                return Err(reader.error("expected `/`", reader.position));
+            };
+        }
+        Ok(Query { steps })
+    }
+
+    /// Selects from the values of `lino`, such as a document returned by
+    /// [`crate::parse_lino`].
+    pub fn select<'a, T: AsRef<str>>(&self, lino: &'a LiNo<T>) -> Vec<&'a LiNo<T>> {
+        let values = match lino {
+            LiNo::Link { values, .. } => values.as_slice(),
+            LiNo::Ref(_) => &[],
+        };
+        Tree::new(Some(lino), values).select(&self.steps)
+    }
+
+    /// Selects from a list of top-level links.
+    pub fn select_in<'a, T: AsRef<str>>(&self, links: &'a [LiNo<T>]) -> Vec<&'a LiNo<T>> {
+        Tree::new(None, links).select(&self.steps)
+    }
+}
+
+/// Runs a LinoPath query on the values of `lino`.
+pub fn query<'a, T: AsRef<str>>(lino: &'a LiNo<T>, query: &str) -> Result<Vec<&'a LiNo<T>>> {
+    Ok(Query::parse(query)?.select(lino))
+}
+
+/// Runs a LinoPath query on a list of top-level links.
+pub fn query_links<'a, T: AsRef<str>>(links: &'a [LiNo<T>], query: &str) -> Result<Vec<&'a LiNo<T>>> {
+    Ok(Query::parse(query)?.select_in(links))
+}
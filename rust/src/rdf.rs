@@ -0,0 +1,622 @@
+//! Conversion between RDF triples and Links Notation.
+//!
+//! Every triple becomes a link of three references, `(subject predicate object)`,
+//! and links of the form `(id: subject predicate object)` are written as
+//! triples too; RDF has no place for the id, so it is left out. RDF terms are
+//! mapped onto references as follows:
+//!
+//! | RDF term                        | Reference                    |
+//! |---------------------------------|------------------------------|
+//! | `<http://example.org/alice>`    | `http://example.org/alice`   |
+//! | `_:b0`                          | `_:b0`                       |
+//! | `"Alice"`, `"Alice"@en`         | `"Alice"`, `"Alice"@en`      |
+//! | `"42"^^<http://…#integer>`      | `"42"^^<http://…#integer>`   |
+//!
+//! Literals keep their N-Triples form, so a reference starting with `"` is a
+//! literal, a reference starting with `_:` is a blank node and any other
+//! reference is an IRI, which must be absolute, starting with a scheme such as
+//! `http:`. Apostrophes in literals are escaped as `\u0027`, so a literal
+//! never contains both quote kinds and can always be written as Links
+//! Notation: `"O'Brien"` becomes the reference `"O\u0027Brien"`.
+//!
+//! Both N-Triples and basic Turtle can be read: `@prefix` and `PREFIX`
+//! directives, prefixed names, `a`, predicate lists with `;`, object lists
+//! with `,`, single and double quoted strings, numbers, booleans and blank
+//! nodes written as `[]` or `[ predicate object ]`, which are given labels
+//! starting with `_:anon`. Collections, long strings and `@base` are not
+//! supported.
+
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::{check_representable, format_links, parse_lino_to_links, LiNo};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+const BLANK_PREFIX: &str = "_:";
+
+fn is_label_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+fn escape_literal(lexical: &str) -> String {
+    let mut result = String::new();
+    for c in lexical.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+fn escape_iri(iri: &str) -> String {
+    let mut result = String::new();
+    for c in iri.chars() {
+        if c <= ' ' || "<>\"{}|^`\\".contains(c) {
+            result.push_str(&format!("\\u{:04X}", c as u32));
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+enum Suffix {
+    None,
+    Language(String),
+    Datatype(String),
+}
+
+fn literal(lexical: &str, suffix: Suffix) -> String {
+    match suffix {
+        Suffix::Language(language) => format!("\"{}\"@{}", escape_literal(lexical), language),
+        Suffix::Datatype(datatype) if datatype != format!("{}string", XSD) => {
+            format!("\"{}\"^^<{}>", escape_literal(lexical), escape_iri(&datatype))
+        }
+        _ => format!("\"{}\"", escape_literal(lexical)),
+    }
+}
+
+// The reference of a literal, with apostrophes escaped so that it can be quoted
+fn literal_reference(lexical: &str, suffix: Suffix) -> String {
+    literal(lexical, suffix).replace('\'', "\\u0027")
+}
+
+struct Parser<'a> {
+    document: &'a str,
+    position: usize,
+    turtle: bool,
+    prefixes: HashMap<String, String>,
+    anonymous: usize,
+    triples: Vec<LiNo<String>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(document: &'a str, turtle: bool) -> Self {
+        Parser {
+            document,
+            position: 0,
+            turtle,
+            prefixes: HashMap::new(),
+            anonymous: 0,
+            triples: vec![],
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::new(message).at(self.document, self.position)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.document[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+            if trimmed.starts_with('#') {
+                self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip();
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", expected)))
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c: char| !predicate(c)).unwrap_or(rest.len());
+        self.position += end;
+        &rest[..end]
+    }
+
+    // Like `take_while`, but leaves a trailing `.` to end the statement
+    fn take_name(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let name = self.take_while(predicate);
+        let trimmed = name.trim_end_matches('.');
+        self.position -= name.len() - trimmed.len();
+        trimmed
+    }
+
+    fn push(&mut self, subject: &str, predicate: &str, object: String) {
+        self.triples.push(LiNo::Link {
+            id: None,
+            values: vec![
+                LiNo::Ref(subject.to_string()),
+                LiNo::Ref(predicate.to_string()),
+                LiNo::Ref(object),
+            ],
+        });
+    }
+
+    fn parse(mut self) -> Result<Vec<LiNo<String>>> {
+        loop {
+            self.skip();
+            if self.rest().is_empty() {
+                return Ok(self.triples);
+            }
+            if self.turtle && self.directive()? {
+                continue;
+            }
+            let subject = self.subject()?;
+            self.predicate_objects(&subject)?;
+            self.expect('.')?;
+        }
+    }
+
+    fn keyword(&self, keyword: &str) -> bool {
+        let rest = self.rest();
+        rest.get(..keyword.len()).is_some_and(|word| word.eq_ignore_ascii_case(keyword))
+            && rest[keyword.len()..].starts_with(char::is_whitespace)
+    }
+
+    // Reads a `@prefix` or `PREFIX` directive, returning whether there was one
+    fn directive(&mut self) -> Result<bool> {
+        if self.keyword("@base") || self.keyword("base") {
+            return Err(self.error("`@base` is not supported"));
+        }
+        let sparql = self.keyword("prefix");
+        if !sparql && !self.rest().starts_with("@prefix") {
+            return Ok(false);
+        }
+        self.position += if sparql { 6 } else { 7 };
+        self.skip();
+        let prefix = self.take_while(|c| c != ':' && is_label_char(c)).to_string();
+        self.expect(':')?;
+        self.skip();
+        if self.peek() != Some('<') {
+            return Err(self.error("expected an IRI"));
+        }
+        let iri = self.iri()?;
+        self.prefixes.insert(prefix, iri);
+        if !sparql {
+            self.expect('.')?;
+        }
+        Ok(true)
+    }
+
+    fn subject(&mut self) -> Result<String> {
+        self.skip();
+        match self.peek() {
+            Some('<') => self.iri(),
+            Some('_') => self.blank_node(),
+            Some('[') if self.turtle => self.anonymous_node(),
+            Some(_) if self.turtle && !self.rest().starts_with(['"', '\'']) => self.prefixed_name(),
+            _ => Err(self.error("expected a subject IRI or blank node")),
+        }
+    }
+
+    fn predicate(&mut self) -> Result<String> {
+        self.skip();
+        match self.peek() {
+            Some('<') => self.iri(),
+            Some('a') if self.turtle && self.rest()[1..].starts_with(|c: char| c.is_whitespace()) => {
+                self.position += 1;
+                Ok(RDF_TYPE.to_string())
+            }
+            Some(c) if self.turtle && c != '"' && c != '\'' && c != '_' && c != '[' => self.prefixed_name(),
+            _ => Err(self.error("expected a predicate IRI")),
+        }
+    }
+
+    fn object(&mut self) -> Result<String> {
+        self.skip();
+        match self.peek() {
+            Some('<') => self.iri(),
+            Some('_') => self.blank_node(),
+            Some('"') => self.literal('"'),
+            Some('\'') if self.turtle => self.literal('\''),
+            Some('[') if self.turtle => self.anonymous_node(),
+            Some('(') if self.turtle => Err(self.error("collections are not supported")),
+            Some(c) if self.turtle && (c.is_ascii_digit() || c == '+' || c == '-' || c == '.') => self.number(),
+            Some(_) if self.turtle => self.boolean_or_prefixed_name(),
+            _ => Err(self.error("expected an object")),
+        }
+    }
+
+    fn predicate_objects(&mut self, subject: &str) -> Result<()> {
+        loop {
+            let predicate = self.predicate()?;
+            loop {
+                let object = self.object()?;
+                self.push(subject, &predicate, object);
+                self.skip();
+                if self.turtle && self.peek() == Some(',') {
+                    self.position += 1;
+                } else {
+                    break;
+                }
+            }
+            if !(self.turtle && self.peek() == Some(';')) {
+                return Ok(());
+            }
+            while self.peek() == Some(';') {
+                self.position += 1;
+                self.skip();
+            }
+            if matches!(self.peek(), Some('.') | Some(']')) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn anonymous_node(&mut self) -> Result<String> {
+        self.position += 1;
+        self.anonymous += 1;
+        let node = format!("{}anon{}", BLANK_PREFIX, self.anonymous);
+        self.skip();
+        if self.peek() != Some(']') {
+            self.predicate_objects(&node)?;
+        }
+        self.expect(']')?;
+        Ok(node)
+    }
+
+    fn unicode_escape(&mut self, chars: &mut std::str::Chars, length: usize) -> Result<char> {
+        let digits: String = chars.take(length).collect();
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .filter(|_| digits.len() == length)
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn iri(&mut self) -> Result<String> {
+        let start = self.position;
+        let rest = &self.rest()[1..];
+        let end = rest.find('>').ok_or_else(|| self.error("unterminated IRI"))?;
+        let mut iri = String::new();
+        let mut chars = rest[..end].chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('u') => iri.push(self.unicode_escape(&mut chars, 4)?),
+                    Some('U') => iri.push(self.unicode_escape(&mut chars, 8)?),
+                    _ => return Err(self.error("invalid escape in IRI")),
+                },
+                c if c <= ' ' || "<\"{}|^`".contains(c) => {
+                    return Err(self.error(format!("invalid character {:?} in IRI", c)))
+                }
+                c => iri.push(c),
+            }
+        }
+        self.position = start + end + 2;
+        Ok(iri)
+    }
+
+    fn blank_node(&mut self) -> Result<String> {
+        if !self.rest().starts_with(BLANK_PREFIX) {
+            return Err(self.error("expected a blank node"));
+        }
+        self.position += BLANK_PREFIX.len();
+        let label = self.take_name(is_label_char);
+        if label.is_empty() {
+            return Err(self.error("expected a blank node label"));
+        }
+        Ok(format!("{}{}", BLANK_PREFIX, label))
+    }
+
+    fn prefixed_name(&mut self) -> Result<String> {
+        let start = self.position;
+        let prefix = self.take_while(|c| c != ':' && is_label_char(c));
+        if self.peek() != Some(':') {
+            self.position = start;
+            return Err(self.error("expected a prefixed name"));
+        }
+        self.position += 1;
+        let local = self.take_name(|c| is_label_char(c) || c == ':' || c == '%');
+        match self.prefixes.get(prefix) {
+            Some(namespace) => Ok(format!("{}{}", namespace, local)),
+            None => {
+                self.position = start;
+                Err(self.error(format!("undefined prefix `{}:`", prefix)))
+            }
+        }
+    }
+
+    fn literal(&mut self, quote: char) -> Result<String> {
+        let (lexical, suffix) = self.literal_parts(quote)?;
+        Ok(literal_reference(&lexical, suffix))
+    }
+
+    fn literal_parts(&mut self, quote: char) -> Result<(String, Suffix)> {
+        let start = self.position;
+        self.position += 1;
+        let mut lexical = String::new();
+        let mut chars = self.rest().chars();
+        let mut length = 1;
+        loop {
+            let c = chars.next().ok_or_else(|| self.error("unterminated string"))?;
+            length += c.len_utf8();
+            match c {
+                c if c == quote => break,
+                '\n' | '\r' => return Err(self.error("line break in string")),
+                '\\' => {
+                    let escaped = chars.next().ok_or_else(|| self.error("unterminated string"))?;
+                    length += 1;
+                    match escaped {
+                        't' => lexical.push('\t'),
+                        'b' => lexical.push('\u{8}'),
+                        'n' => lexical.push('\n'),
+                        'r' => lexical.push('\r'),
+                        'f' => lexical.push('\u{c}'),
+                        '"' | '\'' | '\\' => lexical.push(escaped),
+                        'u' => {
+                            lexical.push(self.unicode_escape(&mut chars, 4)?);
+                            length += 4;
+                        }
+                        'U' => {
+                            lexical.push(self.unicode_escape(&mut chars, 8)?);
+                            length += 8;
+                        }
+                        _ => return Err(self.error("invalid escape in string")),
+                    }
+                }
+                c => lexical.push(c),
+            }
+        }
+        self.position = start + length;
+
+        let suffix = if self.rest().starts_with('@') {
+            self.position += 1;
+            let language = self.take_while(|c| c.is_ascii_alphanumeric() || c == '-');
+            if language.is_empty() {
+                return Err(self.error("expected a language tag"));
+            }
+            Suffix::Language(language.to_string())
+        } else if self.rest().starts_with("^^") {
+            self.position += 2;
+            Suffix::Datatype(match self.peek() {
+                Some('<') => self.iri()?,
+                _ if self.turtle => self.prefixed_name()?,
+                _ => return Err(self.error("expected a datatype IRI")),
+            })
+        } else {
+            Suffix::None
+        };
+        Ok((lexical, suffix))
+    }
+
+    fn number(&mut self) -> Result<String> {
+        let number = self.take_name(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+        let datatype = if number.contains(['e', 'E']) {
+            "double"
+        } else if number.contains('.') {
+            "decimal"
+        } else {
+            "integer"
+        };
+        if number.parse::<f64>().is_err() {
+            return Err(self.error(format!("invalid number `{}`", number)));
+        }
+        Ok(literal_reference(number, Suffix::Datatype(format!("{}{}", XSD, datatype))))
+    }
+
+    fn boolean_or_prefixed_name(&mut self) -> Result<String> {
+        for value in ["true", "false"] {
+            let rest = self.rest();
+            if rest.starts_with(value) && !rest[value.len()..].starts_with(|c: char| is_label_char(c) || c == ':') {
+                self.position += value.len();
+                return Ok(literal_reference(value, Suffix::Datatype(format!("{}boolean", XSD))));
+            }
+        }
+        self.prefixed_name()
+    }
+}
+
+/// Reads an N-Triples document as links of three references.
+pub fn ntriples_to_lino(document: &str) -> Result<Vec<LiNo<String>>> {
+    Parser::new(document, false).parse()
+}
+
+/// Reads a Turtle document as links of three references.
+pub fn turtle_to_lino(document: &str) -> Result<Vec<LiNo<String>>> {
+    Parser::new(document, true).parse()
+}
+
+enum Term {
+    Iri(String),
+    Blank(String),
+    Literal(String, Suffix),
+}
+
+// Whether an IRI starts with a scheme, as RDF has no relative IRIs
+fn is_absolute(iri: &str) -> bool {
+    match iri.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        None => false,
+    }
+}
+
+fn term(reference: &str) -> Result<Term> {
+    if reference.starts_with('"') {
+        let mut parser = Parser::new(reference, false);
+        return match parser.literal_parts('"') {
+            Ok((lexical, suffix)) if parser.rest().is_empty() => Ok(Term::Literal(lexical, suffix)),
+            _ => Err(Error::new(format!("invalid literal {}", reference))),
+        };
+    }
+    if let Some(label) = reference.strip_prefix(BLANK_PREFIX) {
+        if label.is_empty() || !label.chars().all(is_label_char) || label.ends_with('.') {
+            return Err(Error::new(format!("invalid blank node {}", reference)));
+        }
+        return Ok(Term::Blank(reference.to_string()));
+    }
+    if reference.is_empty() {
+        return Err(Error::new("empty IRI"));
+    }
+    if !is_absolute(reference) {
+        return Err(Error::new(format!("`{}` is not an absolute IRI", reference)));
+    }
+    Ok(Term::Iri(reference.to_string()))
+}
+
+fn triple<T: ToString>(link: &LiNo<T>) -> Result<(Term, Term, Term)> {
+    let values = match link {
+        LiNo::Link { values, .. } if values.len() == 3 => values,
+        _ => return Err(Error::new("only links of three references can be written as triples")),
+    };
+    let reference = |value: &LiNo<T>| match value {
+        LiNo::Ref(reference) => term(&reference.to_string()),
+        LiNo::Link { .. } => Err(Error::new("nested links cannot be written as triples")),
+    };
+    let (subject, predicate, object) = (reference(&values[0])?, reference(&values[1])?, reference(&values[2])?);
+    if let Term::Literal(..) = subject {
+        return Err(Error::new("the subject of a triple cannot be a literal"));
+    }
+    if !matches!(predicate, Term::Iri(_)) {
+        return Err(Error::new("the predicate of a triple must be an IRI"));
+    }
+    Ok((subject, predicate, object))
+}
+
+fn write_ntriples_term(term: Term) -> String {
+    match term {
+        Term::Iri(iri) => format!("<{}>", escape_iri(&iri)),
+        Term::Blank(label) => label,
+        Term::Literal(lexical, suffix) => literal(&lexical, suffix),
+    }
+}
+
+/// Writes links of three references as an N-Triples document.
+pub fn lino_to_ntriples<T: ToString>(links: &[LiNo<T>]) -> Result<String> {
+    let mut output = String::new();
+    for link in links {
+        let (subject, predicate, object) = triple(link)?;
+        output.push_str(&format!(
+            "{} {} {} .\n",
+            write_ntriples_term(subject),
+            write_ntriples_term(predicate),
+            write_ntriples_term(object)
+        ));
+    }
+    Ok(output)
+}
+
+fn compact(iri: &str, prefixes: &[(&str, &str)]) -> String {
+    for (prefix, namespace) in prefixes {
+        if let Some(local) = iri.strip_prefix(namespace) {
+            let simple = local.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+                && !local.starts_with('-');
+            if simple {
+                return format!("{}:{}", prefix, local);
+            }
+        }
+    }
+    format!("<{}>", escape_iri(iri))
+}
+
+fn write_turtle_term(term: Term, prefixes: &[(&str, &str)]) -> String {
+    match term {
+        Term::Iri(iri) => compact(&iri, prefixes),
+        Term::Blank(label) => label,
+        Term::Literal(lexical, Suffix::Datatype(datatype)) if datatype != format!("{}string", XSD) => {
+            format!("\"{}\"^^{}", escape_literal(&lexical), compact(&datatype, prefixes))
+        }
+        Term::Literal(lexical, suffix) => literal(&lexical, suffix),
+    }
+}
+
+/// Writes links of three references as a Turtle document, grouping triples
+/// that share a subject and shortening IRIs with `prefixes`, given as
+/// `(prefix, namespace)` pairs.
+pub fn lino_to_turtle<T: ToString>(links: &[LiNo<T>], prefixes: &[(&str, &str)]) -> Result<String> {
+    let mut output = String::new();
+    for (prefix, namespace) in prefixes {
+        output.push_str(&format!("@prefix {}: <{}> .\n", prefix, escape_iri(namespace)));
+    }
+    if !prefixes.is_empty() && !links.is_empty() {
+        output.push('\n');
+    }
+
+    let mut current: Option<String> = None;
+    for link in links {
+        let (subject, predicate, object) = triple(link)?;
+        let subject = write_turtle_term(subject, prefixes);
+        let predicate = match predicate {
+            Term::Iri(iri) if iri == RDF_TYPE => "a".to_string(),
+            predicate => write_turtle_term(predicate, prefixes),
+        };
+        let object = write_turtle_term(object, prefixes);
+        if current.as_ref() == Some(&subject) {
+            output.push_str(&format!(" ;\n    {} {}", predicate, object));
+        } else {
+            if current.is_some() {
+                output.push_str(" .\n");
+            }
+            output.push_str(&format!("{} {} {}", subject, predicate, object));
+            current = Some(subject);
+        }
+    }
+    if current.is_some() {
+        output.push_str(" .\n");
+    }
+    Ok(output)
+}
+
+fn format_triples(links: Vec<LiNo<String>>) -> Result<String> {
+    links.iter().try_for_each(check_representable)?;
+    Ok(format_links(&links, false))
+}
+
+/// Converts an N-Triples document into a Links Notation document.
+pub fn ntriples_to_text(document: &str) -> Result<String> {
+    format_triples(ntriples_to_lino(document)?)
+}
+
+/// Converts the triplets of a Links Notation document into N-Triples.
+pub fn text_to_ntriples(document: &str) -> Result<String> {
+    lino_to_ntriples(&parse_lino_to_links(document).map_err(Error::new)?)
+}
+
+/// Converts a Turtle document into a Links Notation document.
+pub fn turtle_to_text(document: &str) -> Result<String> {
+    format_triples(turtle_to_lino(document)?)
+}
+
+/// Converts the triplets of a Links Notation document into Turtle.
+pub fn text_to_turtle(document: &str, prefixes: &[(&str, &str)]) -> Result<String> {
+    lino_to_turtle(&parse_lino_to_links(document).map_err(Error::new)?, prefixes)
+}
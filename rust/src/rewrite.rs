@@ -0,0 +1,209 @@
+//! Rule-based rewriting of links.
+//!
+//! A rule is a link with a pattern and a replacement, optionally named:
+//!
+//! ```text
+//! (befriend: ($x: $a knows $b) ($x: $a friend $b))
+//! ```
+//!
+//! Patterns use the variables of [`crate::pattern`], and every variable of a
+//! replacement must be bound by its pattern. Each step applies the first rule
+//! that changes the document, at the first link it matches in document order,
+//! replacing that link with the substituted replacement. Rewriting stops at a
+//! fixpoint, when no rule changes anything, or after the maximum number of
+//! steps; without a maximum, rules that keep changing the document never stop.
+
+use crate::error::{Error, Result};
+use crate::pattern::{substitute, variables, Bindings, Pattern};
+use crate::{format_links, parse_lino_to_links, LiNo};
+
+/// A rewrite rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub name: Option<String>,
+    pub pattern: Pattern,
+    pub replacement: LiNo<String>,
+}
+
+impl Rule {
+    /// Creates a rule, checking that the pattern binds every variable of the
+    /// replacement.
+    pub fn new(pattern: Pattern, replacement: LiNo<String>) -> Result<Self> {
+        let bound = pattern.variables();
+        if let Some(unbound) = variables(&replacement).into_iter().find(|name| !bound.contains(name)) {
+            return Err(Error::new(format!("`${}` is not bound by the pattern", unbound)));
+        }
+        Ok(Rule {
+            name: None,
+            pattern,
+            replacement,
+        })
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    fn apply(&self, value: &LiNo<String>) -> Result<Option<LiNo<String>>> {
+        match self.pattern.matches(value) {
+            Some(bindings) => self.replace(&bindings).map(|after| Some(after).filter(|after| after != value)),
+            None => Ok(None),
+        }
+    }
+
+    fn replace(&self, bindings: &Bindings) -> Result<LiNo<String>> {
+        substitute(&self.replacement, bindings).map_err(|error| match &self.name {
+            Some(name) => Error::new(format!("rule `{}`: {}", name, error.message())),
+            None => error,
+        })
+    }
+}
+
+/// A rewrite applied to a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Applied {
+    /// Index of the rule.
+    pub rule: usize,
+    /// Position of the rewritten link: its index among the top-level links,
+    /// followed by its position among the values of each enclosing link.
+    pub path: Vec<usize>,
+    pub before: LiNo<String>,
+    pub after: LiNo<String>,
+}
+
+/// The result of rewriting a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rewrite {
+    pub links: Vec<LiNo<String>>,
+    pub trace: Vec<Applied>,
+    /// Whether rewriting stopped because no rule applied.
+    pub fixpoint: bool,
+}
+
+/// An ordered set of rules.
+#[derive(Debug, Clone, Default)]
+pub struct Rewriter {
+    rules: Vec<Rule>,
+    max_steps: Option<usize>,
+}
+
+fn find(rule: &Rule, value: &LiNo<String>, path: &mut Vec<usize>) -> Result<Option<LiNo<String>>> {
+    if let Some(after) = rule.apply(value)? {
+        return Ok(Some(after));
+    }
+    if let LiNo::Link { values, .. } = value {
+        for (index, value) in values.iter().enumerate() {
+            path.push(index);
+            if let Some(after) = find(rule, value, path)? {
+                return Ok(Some(after));
+            }
+            path.pop();
+        }
+    }
+    Ok(None)
+}
+
+fn at_path<'a>(links: &'a mut [LiNo<String>], path: &[usize]) -> &'a mut LiNo<String> {
+    let mut value = &mut links[path[0]];
+    for &index in &path[1..] {
+        value = match value {
+            LiNo::Link { values, .. } => &mut values[index],
+            LiNo::Ref(_) => unreachable!("paths only lead through links"),
+        };
+    }
+    value
+}
+
+impl Rewriter {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Rewriter { rules, max_steps: None }
+    }
+
+    /// Parses rules written as links with a pattern and a replacement, named
+    /// by their ids.
+    pub fn parse(rules: &str) -> Result<Self> {
+        let mut parsed = vec![];
+        for (index, link) in parse_lino_to_links(rules).map_err(Error::new)?.into_iter().enumerate() {
+            let LiNo::Link { id, mut values } = link else {
+                return Err(Error::new(format!("rule {} is not a link", index + 1)));
+            };
+            if values.len() != 2 {
+                return Err(Error::new(format!(
+                    "rule {} should have a pattern and a replacement, found {} values",
+                    index + 1,
+                    values.len()
+                )));
+            }
+            let replacement = values.pop().unwrap();
+            let pattern = Pattern::new(values.pop().unwrap());
+            let rule = Rule::new(pattern, replacement).map_err(|error| match &id {
+                Some(id) => Error::new(format!("rule `{}`: {}", id, error.message())),
+                None => Error::new(format!("rule {}: {}", index + 1, error.message())),
+            })?;
+            parsed.push(match id {
+                Some(id) => rule.with_name(id),
+                None => rule,
+            });
+        }
+        Ok(Rewriter::new(parsed))
+    }
+
+    /// Stops rewriting after `steps` steps.
+    pub fn with_max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = Some(steps);
+        self
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Applies a single rewrite, returning it, or `None` at a fixpoint.
+    pub fn step(&self, links: &mut [LiNo<String>]) -> Result<Option<Applied>> {
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            for (index, link) in links.iter().enumerate() {
+                let mut path = vec![index];
+                if let Some(after) = find(rule, link, &mut path)? {
+                    let value = at_path(links, &path);
+                    let before = std::mem::replace(value, after.clone());
+                    return Ok(Some(Applied {
+                        rule: rule_index,
+                        path,
+                        before,
+                        after,
+                    }));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Rewrites links until a fixpoint or the maximum number of steps.
+    pub fn run(&self, mut links: Vec<LiNo<String>>) -> Result<Rewrite> {
+        let mut trace = vec![];
+        while self.max_steps.is_none_or(|max| trace.len() < max) {
+            match self.step(&mut links)? {
+                Some(applied) => trace.push(applied),
+                None => {
+                    return Ok(Rewrite {
+                        links,
+                        trace,
+                        fixpoint: true,
+                    })
+                }
+            }
+        }
+        Ok(Rewrite {
+            links,
+            trace,
+            fixpoint: false,
+        })
+    }
+
+    /// Parses a document, rewrites it and writes the result.
+    pub fn run_text(&self, document: &str) -> Result<String> {
+        let links = parse_lino_to_links(document).map_err(Error::new)?;
+        Ok(format_links(&self.run(links)?.links, false))
+    }
+}
@@ -0,0 +1,422 @@
+//! Schemas describing the expected shape of links, and their validation.
+//!
+//! A schema is itself Links Notation. Each definition is a link whose id
+//! selects the links it applies to, with constraints as values:
+//!
+//! ```text
+//! person: (arity: 1 *) (values: ref name age) (requires: name)
+//! name: (arity: 1) (values: ref)
+//! age: (arity: 1) (values: number)
+//! friend: (arity: 2) (references: *)
+//! _: (values: link)
+//! ```
+//!
+//! - `(arity: n)` or `(arity: min max)` limits the number of values, with `*`
+//!   as a maximum meaning no limit;
+//! - `(values: ...)` lists the allowed kinds of values: `ref` for any
+//!   reference, `number` for a reference written as a finite number, `link`
+//!   for any link, or an id for links with that id; `(id: ref)` stands for
+//!   links with the id `ref`, and likewise for the other keywords;
+//! - `(requires: ...)` lists ids of links, or references, that must be among
+//!   the values, such as the children of an indented id;
+//! - `(references: ...)` lists the names references may be; `*` allows any id
+//!   defined in the document.
+//!
+//! Definitions apply at any depth. `_` applies to links without id, and `*` to
+//! links whose id has no definition of its own. Without a `*` definition, ids
+//! that are not defined by the schema are violations; links without id are
+//! only checked when there is a `_` definition.
+//!
+//! Violations are located by their path in the document: the index of the
+//! top-level link followed by the position of each value on the way down, as
+//! in [`crate::visit`]. [`Schema::validate_document`] also reports the line
+//! and column of each violation.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::visit::pre_order;
+use crate::{flatten_links, parse_lino_to_ast, parse_lino_to_links, parser, LiNo};
+
+/// Links a definition applies to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector {
+    /// Links with this id.
+    Id(String),
+    /// Links without id, written `_`.
+    Unnamed,
+    /// Links whose id has no definition, written `*`.
+    Any,
+}
+
+/// A kind of value allowed by `(values: ...)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    Ref,
+    Number,
+    Link,
+    /// A link with this id.
+    Id(String),
+}
+
+/// A name allowed by `(references: ...)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    Name(String),
+    /// Any id defined in the validated document, written `*`.
+    Defined,
+}
+
+/// Constraints on the links selected by a definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub selector: Selector,
+    /// Minimum and optional maximum number of values.
+    pub arity: Option<(usize, Option<usize>)>,
+    pub values: Option<Vec<Kind>>,
+    pub requires: Vec<String>,
+    pub references: Option<Vec<Target>>,
+}
+
+impl Definition {
+    pub fn new(selector: Selector) -> Self {
+        Definition {
+            selector,
+            arity: None,
+            values: None,
+            requires: vec![],
+            references: None,
+        }
+    }
+
+    fn parse(lino: &LiNo<String>) -> Result<Self> {
+        let (id, constraints) = match lino {
+            LiNo::Link { id: Some(id), values } => (id, values),
+            _ => return Err(Error::new(format!("expected a definition `id: constraints`, found `{}`", lino))),
+        };
+        let selector = match id.as_str() {
+            "_" => Selector::Unnamed,
+            "*" => Selector::Any,
+            _ => Selector::Id(id.clone()),
+        };
+        let mut definition = Definition::new(selector);
+        for constraint in constraints {
+            let (name, values) = match constraint {
+                LiNo::Link { id: Some(name), values } => (name.as_str(), values),
+                _ => return Err(Error::new(format!("expected a constraint in `{}`, found `{}`", id, constraint))),
+            };
+            let arguments = || names(values, constraint);
+            match name {
+                "arity" => definition.arity = Some(parse_arity(&arguments()?, constraint)?),
+                "values" => definition.values = Some(kinds(values, constraint)?),
+                "requires" => definition.requires.extend(arguments()?.into_iter().map(str::to_string)),
+                "references" => {
+                    definition.references = Some(
+                        arguments()?
+                            .into_iter()
+                            .map(|name| match name {
+                                "*" => Target::Defined,
+                                _ => Target::Name(name.to_string()),
+                            })
+                            .collect(),
+                    )
+                }
+                _ => return Err(Error::new(format!("unknown constraint `{}` in `{}`", name, id))),
+            }
+        }
+        Ok(definition)
+    }
+}
+
+// Arguments of a constraint, which must all be references
+fn names<'a>(values: &'a [LiNo<String>], constraint: &LiNo<String>) -> Result<Vec<&'a str>> {
+    values
+        .iter()
+        .map(|value| match value {
+            LiNo::Ref(name) => Ok(name.as_str()),
+            LiNo::Link { .. } => Err(Error::new(format!("expected names in `{}`", constraint))),
+        })
+        .collect()
+}
+
+// Arguments of `(values: ...)`, where `(id: name)` is the id `name` even when
+// `name` is a keyword
+fn kinds(values: &[LiNo<String>], constraint: &LiNo<String>) -> Result<Vec<Kind>> {
+    values
+        .iter()
+        .map(|value| match value {
+            LiNo::Ref(name) => Ok(Kind::parse(name)),
+            LiNo::Link { id: Some(id), values } if id == "id" => match values.as_slice() {
+                [LiNo::Ref(name)] => Ok(Kind::Id(name.clone())),
+                _ => Err(Error::new(format!("expected `(id: name)`, found `{}`", value))),
+            },
+            LiNo::Link { .. } => Err(Error::new(format!("expected names in `{}`", constraint))),
+        })
+        .collect()
+}
+
+fn parse_arity(arguments: &[&str], constraint: &LiNo<String>) -> Result<(usize, Option<usize>)> {
+    let invalid = || Error::new(format!("expected `(arity: n)` or `(arity: min max)`, found `{}`", constraint));
+    let count = |text: &str| text.parse::<usize>().map_err(|_| invalid());
+    match arguments {
+        [exact] => Ok((count(exact)?, Some(count(exact)?))),
+        [min, "*"] => Ok((count(min)?, None)),
+        [min, max] if count(min)? <= count(max)? => Ok((count(min)?, Some(count(max)?))),
+        _ => Err(invalid()),
+    }
+}
+
+impl Kind {
+    fn parse(name: &str) -> Self {
+        match name {
+            "ref" => Kind::Ref,
+            "number" => Kind::Number,
+            "link" => Kind::Link,
+            _ => Kind::Id(name.to_string()),
+        }
+    }
+
+    fn matches(&self, value: &LiNo<String>) -> bool {
+        match (self, value) {
+            (Kind::Ref, LiNo::Ref(_)) | (Kind::Link, LiNo::Link { .. }) => true,
+            (Kind::Number, LiNo::Ref(text)) => is_number(text),
+            (Kind::Id(id), LiNo::Link { id: Some(value), .. }) => id == value,
+            _ => false,
+        }
+    }
+}
+
+// Finite numbers only: `f64` also parses `NaN`, `inf` and `infinity`
+fn is_number(text: &str) -> bool {
+    let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+    digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') && text.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Ref => write!(f, "ref"),
+            Kind::Number => write!(f, "number"),
+            Kind::Link => write!(f, "link"),
+            Kind::Id(id) => write!(f, "`{}`", id),
+        }
+    }
+}
+
+/// A link that does not conform to a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// Path of the link in the document.
+    pub path: Vec<usize>,
+    /// 1-based line and column of the link, when validating a document.
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path: Vec<String> = self.path.iter().map(usize::to_string).collect();
+        write!(f, "{} at {}", self.message, path.join("/"))?;
+        if let Some((line, column)) = self.location {
+            write!(f, " (line {}, column {})", line, column)?;
+        }
+        Ok(())
+    }
+}
+
+/// A set of definitions that documents are validated against.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schema {
+    definitions: Vec<Definition>,
+}
+
+impl Schema {
+    /// Creates a schema, failing if two definitions have the same selector.
+    pub fn new(definitions: Vec<Definition>) -> Result<Self> {
+        for (index, definition) in definitions.iter().enumerate() {
+            if definitions[..index].iter().any(|other| other.selector == definition.selector) {
+                return Err(Error::new(format!(
+                    "duplicate definition {}",
+                    match &definition.selector {
+                        Selector::Id(id) => format!("`{}`", id),
+                        Selector::Unnamed => "`_`".to_string(),
+                        Selector::Any => "`*`".to_string(),
+                    }
+                )));
+            }
+        }
+        Ok(Schema { definitions })
+    }
+
+    /// Reads a schema from its links.
+    pub fn from_links(links: &[LiNo<String>]) -> Result<Self> {
+        Self::new(links.iter().map(Definition::parse).collect::<Result<_>>()?)
+    }
+
+    /// Parses a schema written in Links Notation.
+    pub fn parse(document: &str) -> Result<Self> {
+        Self::from_links(&parse_lino_to_links(document).map_err(Error::new)?)
+    }
+
+    pub fn definitions(&self) -> &[Definition] {
+        &self.definitions
+    }
+
+    /// The definition applying to a link with `id`.
+    pub fn definition_for(&self, id: Option<&str>) -> Option<&Definition> {
+        let find = |selector: &Selector| self.definitions.iter().find(|definition| &definition.selector == selector);
+        match id {
+            Some(id) => find(&Selector::Id(id.to_string())).or_else(|| find(&Selector::Any)),
+            None => find(&Selector::Unnamed),
+        }
+    }
+
+    /// Checks every link of a document, returning all violations in document
+    /// order.
+    pub fn validate(&self, links: &[LiNo<String>]) -> Vec<Violation> {
+        self.check(links, |_| None)
+    }
+
+    /// Parses and validates a document, locating violations by line and
+    /// column.
+    pub fn validate_document(&self, document: &str) -> Result<Vec<Violation>> {
+        let ast = parse_lino_to_ast(document).map_err(Error::new)?;
+        let mut sources = vec![];
+        collect_sources(&ast, None, &mut sources);
+        Ok(self.check(&flatten_links(&ast), |path| {
+            locate(&sources, path).map(|offset| parser::line_column(document, offset))
+        }))
+    }
+
+    fn check(&self, links: &[LiNo<String>], locate: impl Fn(&[usize]) -> Option<(usize, usize)>) -> Vec<Violation> {
+        let defined: HashSet<&str> = pre_order(links)
+            .filter_map(|node| node.lino.id())
+            .map(String::as_str)
+            .collect();
+        let mut violations = vec![];
+        for node in pre_order(links) {
+            let LiNo::Link { id, values } = node.lino else {
+                continue;
+            };
+            let mut report = |message: String| {
+                violations.push(Violation {
+                    path: node.path.clone(),
+                    location: locate(&node.path),
+                    message,
+                })
+            };
+            let definition = match self.definition_for(id.as_deref()) {
+                Some(definition) => definition,
+                None => {
+                    if let Some(id) = id {
+                        report(format!("id `{}` is not allowed", id));
+                    }
+                    continue;
+                }
+            };
+            let name = match id {
+                Some(id) => format!("`{}`", id),
+                None => "link without id".to_string(),
+            };
+            check_definition(definition, &name, values, &defined, &mut report);
+        }
+        violations
+    }
+}
+
+// Whether the children of an indented id are its values
+fn is_indented_id(link: &parser::Link) -> bool {
+    link.is_indented_id && link.id.is_some() && link.values.is_empty() && !link.children.is_empty()
+}
+
+// The parser links that top-level links are read from, in document order,
+// with the index of the top-level link of their parent. An indented child is
+// read as a link of its parent's top-level link and itself.
+fn collect_sources<'a>(links: &'a [parser::Link], parent: Option<usize>, sources: &mut Vec<(&'a parser::Link, Option<usize>)>) {
+    for link in links {
+        sources.push((link, parent));
+        if !is_indented_id(link) {
+            collect_sources(&link.children, Some(sources.len() - 1), sources);
+        }
+    }
+}
+
+// The offset of the link at `path`, or of the closest link containing it that
+// is written in the document
+fn locate(sources: &[(&parser::Link, Option<usize>)], path: &[usize]) -> Option<usize> {
+    let (link, parent) = *sources.get(*path.first()?)?;
+    match (parent, path.get(1)) {
+        (None, _) => Some(descend(link, &path[1..])),
+        (Some(parent), Some(0)) => locate(sources, &[&[parent], &path[2..]].concat()),
+        (Some(_), Some(1)) => Some(descend(link, &path[2..])),
+        (Some(_), _) => Some(link.offset),
+    }
+}
+
+fn descend(mut link: &parser::Link, path: &[usize]) -> usize {
+    for &index in path {
+        let values = if is_indented_id(link) { &link.children } else { &link.values };
+        match values.get(index) {
+            Some(value) => link = value,
+            None => break,
+        }
+    }
+    link.offset
+}
+
+fn check_definition(
+    definition: &Definition,
+    name: &str,
+    values: &[LiNo<String>],
+    defined: &HashSet<&str>,
+    report: &mut impl FnMut(String),
+) {
+    if let Some((min, max)) = definition.arity {
+        if values.len() < min || max.is_some_and(|max| values.len() > max) {
+            let expected = match max {
+                Some(max) if max == min => format!("{}", min),
+                Some(max) => format!("{} to {}", min, max),
+                None => format!("at least {}", min),
+            };
+            report(format!("{} has {} values, expected {}", name, values.len(), expected));
+        }
+    }
+    if let Some(kinds) = &definition.values {
+        for (index, value) in values.iter().enumerate() {
+            if !kinds.iter().any(|kind| kind.matches(value)) {
+                let kinds: Vec<String> = kinds.iter().map(Kind::to_string).collect();
+                report(format!(
+                    "value {} of {} is `{}`, expected {}",
+                    index + 1,
+                    name,
+                    value,
+                    kinds.join(" or ")
+                ));
+            }
+        }
+    }
+    for required in &definition.requires {
+        let present = values.iter().any(|value| match value {
+            LiNo::Ref(reference) => reference == required,
+            LiNo::Link { id, .. } => id.as_ref() == Some(required),
+        });
+        if !present {
+            report(format!("{} is missing required `{}`", name, required));
+        }
+    }
+    if let Some(targets) = &definition.references {
+        for value in values {
+            let LiNo::Ref(reference) = value else {
+                continue;
+            };
+            let allowed = targets.iter().any(|target| match target {
+                Target::Name(target) => target == reference,
+                Target::Defined => defined.contains(reference.as_str()),
+            });
+            if !allowed {
+                report(format!("reference `{}` in {} does not name an allowed target", reference, name));
+            }
+        }
+    }
+}
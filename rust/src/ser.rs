@@ -0,0 +1,376 @@
+//! Serde serializer producing Links Notation.
+//!
+//! Values are mapped onto `LiNo<String>` as follows:
+//!
+//! - booleans, numbers, chars and strings become references;
+//! - `None`, `()`, unit structs and empty strings become the empty link `()`;
+//! - sequences and tuples become links of their elements, `(1 2 3)`;
+//! - structs and maps become links of `(field: value)` links;
+//! - enum variants are tagged with the variant name: `Unit`, `(Newtype: value)`,
+//!   `(Tuple: a b)` and `(Struct: (field: value))`.
+//!
+//! At the top level of a document a struct, map or sequence is written one
+//! field or element per line.
+//!
+//! The empty link is shared: `None`, `()`, unit structs, `""` and empty
+//! sequences, maps and structs all serialize to `()`, so they cannot be told
+//! apart in the output.
+
+use serde::ser::{self, Serialize};
+
+use crate::error::{Error, Result};
+use crate::{format_links, LiNo, QuoteStyle};
+
+/// Converts `value` into a `LiNo<String>` tree.
+pub fn to_lino<T: ?Sized + Serialize>(value: &T) -> Result<LiNo<String>> {
+    value.serialize(Serializer)
+}
+
+/// Serializes `value` as a Links Notation document.
+pub fn to_string<T: ?Sized + Serialize>(value: &T) -> Result<String> {
+    let links = match to_lino(value)? {
+        LiNo::Link { id: None, values } => values,
+        other => vec![other],
+    };
+    Ok(format_links(&links, true))
+}
+
+fn empty_link() -> LiNo<String> {
+    LiNo::Link { id: None, values: vec![] }
+}
+
+/// Serializer that builds `LiNo<String>` values.
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = LiNo<String>;
+    type Error = Error;
+
+    type SerializeSeq = SerializeLink;
+    type SerializeTuple = SerializeLink;
+    type SerializeTupleStruct = SerializeLink;
+    type SerializeTupleVariant = SerializeLink;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeLink;
+    type SerializeStructVariant = SerializeLink;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
+        Ok(LiNo::Ref(v.to_string()))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+        Ok(LiNo::Ref(v.to_string()))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok> {
+        Ok(LiNo::Ref(v.to_string()))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+        Ok(LiNo::Ref(v.to_string()))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok> {
+        Ok(LiNo::Ref(v.to_string()))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
+        Ok(LiNo::Ref(v.to_string()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
+        Ok(LiNo::Ref(v.to_string()))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        if v.is_empty() {
+            return Ok(empty_link());
+        }
+        if !QuoteStyle::minimal_for(v).can_represent(v) {
+            return Err(Error::new(format!(
+                "string {:?} needs quotes but contains both single and double quotes, so it cannot be written as a reference",
+                v
+            )));
+        }
+        Ok(LiNo::Ref(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        let values = v.iter().map(|byte| LiNo::Ref(byte.to_string())).collect();
+        Ok(LiNo::Link { id: None, values })
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Ok(empty_link())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok> {
+        Ok(empty_link())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok> {
+        Ok(empty_link())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok> {
+        Ok(LiNo::Ref(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok> {
+        Ok(LiNo::Link {
+            id: Some(variant.to_string()),
+            values: vec![value.serialize(Serializer)?],
+        })
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(SerializeLink::new(None, len.unwrap_or(0)))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        Ok(SerializeLink::new(None, len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Ok(SerializeLink::new(None, len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(SerializeLink::new(Some(variant), len))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(SerializeMap {
+            values: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        Ok(SerializeLink::new(None, len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(SerializeLink::new(Some(variant), len))
+    }
+}
+
+/// Collects the values of a sequence, tuple, struct or enum variant.
+pub struct SerializeLink {
+    id: Option<String>,
+    values: Vec<LiNo<String>>,
+}
+
+impl SerializeLink {
+    fn new(id: Option<&str>, len: usize) -> Self {
+        SerializeLink {
+            id: id.map(str::to_string),
+            values: Vec::with_capacity(len),
+        }
+    }
+
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.values.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn push_field<T: ?Sized + Serialize>(&mut self, key: &str, value: &T) -> Result<()> {
+        self.values.push(LiNo::Link {
+            id: Some(key.to_string()),
+            values: vec![value.serialize(Serializer)?],
+        });
+        Ok(())
+    }
+
+    fn finish(self) -> LiNo<String> {
+        LiNo::Link {
+            id: self.id,
+            values: self.values,
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeLink {
+    type Ok = LiNo<String>;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for SerializeLink {
+    type Ok = LiNo<String>;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeLink {
+    type Ok = LiNo<String>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeLink {
+    type Ok = LiNo<String>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for SerializeLink {
+    type Ok = LiNo<String>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.push_field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeLink {
+    type Ok = LiNo<String>;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<()> {
+        self.push_field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(self.finish())
+    }
+}
+
+/// Collects map entries as `(key: value)` links.
+pub struct SerializeMap {
+    values: Vec<LiNo<String>>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = LiNo<String>;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
+        match key.serialize(Serializer)? {
+            LiNo::Ref(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(Error::new("map keys must serialize to non-empty scalar values")),
+        }
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::new("serialize_value called before serialize_key"))?;
+        self.values.push(LiNo::Link {
+            id: Some(key),
+            values: vec![value.serialize(Serializer)?],
+        });
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(LiNo::Link {
+            id: None,
+            values: self.values,
+        })
+    }
+}
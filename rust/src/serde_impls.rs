@@ -0,0 +1,100 @@
+//! `Serialize` and `Deserialize` for `LiNo<T>`.
+//!
+//! A reference is represented by its value alone and a link by a map with an
+//! `id` (null when absent) and a `values` array. In JSON, `(papa: loves mama)`
+//! becomes:
+//!
+//! ```json
+//! {"id": "papa", "values": ["loves", "mama"]}
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, IntoDeserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::LiNo;
+
+impl<T: Serialize> Serialize for LiNo<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            LiNo::Ref(value) => value.serialize(serializer),
+            LiNo::Link { id, values } => {
+                let mut link = serializer.serialize_struct("Link", 2)?;
+                link.serialize_field("id", id)?;
+                link.serialize_field("values", values)?;
+                link.end()
+            }
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for LiNo<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(LiNoVisitor(PhantomData))
+    }
+}
+
+struct LiNoVisitor<T>(PhantomData<T>);
+
+impl<T> LiNoVisitor<T> {
+    fn reference<'de, E, V>(value: V) -> Result<LiNo<T>, E>
+    where
+        T: Deserialize<'de>,
+        E: de::Error,
+        V: IntoDeserializer<'de, E>,
+    {
+        T::deserialize(value.into_deserializer()).map(LiNo::Ref)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for LiNoVisitor<T> {
+    type Value = LiNo<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a reference or a link with `id` and `values`")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+        Self::reference(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Self::reference(value)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Self::reference(value)
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        Self::reference(value)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Self::reference(value)
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Self::reference(value)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut id: Option<Option<T>> = None;
+        let mut values: Option<Vec<LiNo<T>>> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "id" if id.is_some() => return Err(de::Error::duplicate_field("id")),
+                "id" => id = Some(map.next_value()?),
+                "values" if values.is_some() => return Err(de::Error::duplicate_field("values")),
+                "values" => values = Some(map.next_value()?),
+                other => return Err(de::Error::unknown_field(other, &["id", "values"])),
+            }
+        }
+        Ok(LiNo::Link {
+            id: id.flatten(),
+            values: values.unwrap_or_default(),
+        })
+    }
+}
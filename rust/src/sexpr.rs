@@ -0,0 +1,255 @@
+//! Conversion between S-expressions and Links Notation.
+//!
+//! S-expressions are mapped onto `LiNo` as follows:
+//!
+//! | S-expression      | Links Notation   |
+//! |-------------------|------------------|
+//! | `atom`, `42`      | `atom`, `42`     |
+//! | `"some text"`     | `'some text'`    |
+//! | `(a b c)`         | `(a b c)`        |
+//! | `()`              | `()`             |
+//! | `(id : a b)`      | `(id: a b)`      |
+//! | `(a . b)`         | `(a . b)`        |
+//!
+//! Atoms and strings both become references; when writing, references that
+//! are not valid atoms are written as strings. A list whose second element is
+//! the atom `:` is a named link, with its first element (an atom or a string)
+//! as the id. The dot of a dotted pair is kept as a `.` reference, so
+//! `(a b . c)` reads back as the same improper list. Comments starting with
+//! `;` are skipped.
+
+use crate::error::{Error, Result};
+use crate::{check_representable, format_links, parse_lino_to_links, LiNo};
+
+const NAME_MARKER: &str = ":";
+const DOT: &str = ".";
+
+// A parsed item, remembering whether it was a bare atom so `:` and `.` keep their meaning
+enum Item {
+    Atom(String),
+    Node(LiNo<String>),
+}
+
+impl Item {
+    fn is_atom(&self, atom: &str) -> bool {
+        matches!(self, Item::Atom(value) if value == atom)
+    }
+
+    fn into_lino(self) -> LiNo<String> {
+        match self {
+            Item::Atom(value) => LiNo::Ref(value),
+            Item::Node(node) => node,
+        }
+    }
+}
+
+struct Reader<'a> {
+    document: &'a str,
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn error(&self, message: impl Into<String>, offset: usize) -> Error {
+        Error::new(message).at(self.document, offset)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.document[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == ';' {
+                let rest = &self.document[self.position..];
+                self.position += rest.find('\n').unwrap_or(rest.len());
+            } else if c.is_whitespace() {
+                self.position += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn item(&mut self) -> Result<Item> {
+        let start = self.position;
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                self.list(start).map(Item::Node)
+            }
+            Some(')') => Err(self.error("unexpected `)`", start)),
+            Some('"') => self.string().map(|value| Item::Node(LiNo::Ref(value))),
+            Some(_) => {
+                let rest = &self.document[start..];
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || "()\";".contains(c))
+                    .unwrap_or(rest.len());
+                self.position += end;
+                Ok(Item::Atom(rest[..end].to_string()))
+            }
+            None => Err(self.error("unexpected end of input", start)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let start = self.position;
+        self.position += 1;
+        let mut value = String::new();
+        let mut chars = self.document[self.position..].char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.position += index + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, 'r')) => value.push('\r'),
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        Err(self.error("unterminated string", start))
+    }
+
+    fn list(&mut self, start: usize) -> Result<LiNo<String>> {
+        let mut items = vec![];
+        let mut dot = None;
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(')') {
+                self.position += 1;
+                break;
+            }
+            if self.peek().is_none() {
+                return Err(self.error("unclosed `(`", start));
+            }
+            let offset = self.position;
+            let item = self.item()?;
+            if matches!(dot, Some(dot) if items.len() > dot + 1) {
+                return Err(self.error("expected `)` after the tail of a dotted pair", offset));
+            }
+            if item.is_atom(DOT) {
+                if items.is_empty() || dot.is_some() {
+                    return Err(self.error("unexpected `.`", offset));
+                }
+                dot = Some(items.len());
+            }
+            items.push(item);
+        }
+        if let Some(dot) = dot {
+            if dot + 1 == items.len() {
+                return Err(self.error("missing tail of a dotted pair", self.position - 1));
+            }
+        }
+
+        if items.len() >= 2 && items[1].is_atom(NAME_MARKER) {
+            if let Item::Atom(id) | Item::Node(LiNo::Ref(id)) = &items[0] {
+                let id = id.clone();
+                return Ok(LiNo::Link {
+                    id: Some(id),
+                    values: items.into_iter().skip(2).map(Item::into_lino).collect(),
+                });
+            }
+        }
+        Ok(LiNo::Link {
+            id: None,
+            values: items.into_iter().map(Item::into_lino).collect(),
+        })
+    }
+}
+
+/// Reads every top-level S-expression of `document` as a `LiNo<String>` link.
+pub fn sexpr_to_lino(document: &str) -> Result<Vec<LiNo<String>>> {
+    let mut reader = Reader { document, position: 0 };
+    let mut links = vec![];
+    loop {
+        reader.skip_whitespace();
+        if reader.peek().is_none() {
+            return Ok(links);
+        }
+        let offset = reader.position;
+        match reader.item()? {
+            item if item.is_atom(DOT) => return Err(reader.error("unexpected `.`", offset)),
+            item => links.push(item.into_lino()),
+        }
+    }
+}
+
+fn is_atom(value: &str) -> bool {
+    !value.is_empty()
+        && value != DOT
+        && value != NAME_MARKER
+        && !value.contains(|c: char| c.is_whitespace() || "()\";'\\".contains(c))
+}
+
+fn quoted(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn write_atom(value: &str) -> String {
+    if is_atom(value) {
+        value.to_string()
+    } else {
+        quoted(value)
+    }
+}
+
+/// Writes a link as an S-expression.
+pub fn lino_to_sexpr<T: ToString>(lino: &LiNo<T>) -> String {
+    match lino {
+        LiNo::Ref(value) => write_atom(&value.to_string()),
+        LiNo::Link { id, values } => {
+            let mut items = vec![];
+            if let Some(id) = id {
+                items.push(write_atom(&id.to_string()));
+                items.push(NAME_MARKER.to_string());
+            }
+            let first = items.len();
+            for value in values {
+                items.push(lino_to_sexpr(value));
+            }
+            // A `.` reference is only written as a dot where it forms a valid dotted pair
+            for index in first..items.len() {
+                let is_dot = matches!(&values[index - first], LiNo::Ref(value) if value.to_string() == DOT);
+                if is_dot && index > 0 && index + 2 == items.len() {
+                    items[index] = DOT.to_string();
+                }
+            }
+            format!("({})", items.join(" "))
+        }
+    }
+}
+
+/// Writes links as S-expressions, one per line.
+pub fn links_to_sexpr<T: ToString>(links: &[LiNo<T>]) -> String {
+    links.iter().map(lino_to_sexpr).collect::<Vec<_>>().join("\n")
+}
+
+/// Converts S-expressions into a Links Notation document, one link per line.
+pub fn sexpr_to_text(document: &str) -> Result<String> {
+    let links = sexpr_to_lino(document)?;
+    links.iter().try_for_each(check_representable)?;
+    Ok(format_links(&links, false))
+}
+
+/// Converts a Links Notation document into S-expressions, one per line.
+pub fn text_to_sexpr(document: &str) -> Result<String> {
+    let links = parse_lino_to_links(document).map_err(Error::new)?;
+    Ok(links_to_sexpr(&links))
+}
@@ -0,0 +1,293 @@
+//! In-memory store of links with numeric addresses.
+//!
+//! Every link in a [`LinkStore`] has an address, starting at 1, and a list of
+//! values that are addresses of other links. Names are attached to addresses:
+//! ingesting `(papa: loves mama)` stores a link named `papa` whose values are
+//! the links named `loves` and `mama`. A name that is referenced before it is
+//! defined, or never defined, gets a point link with no values, which its
+//! definition fills in later, so forward references and cycles are kept.
+//!
+//! Unnamed links with identical values are stored once. Nested links become
+//! links of their own, and unnamed links that are only used inside other links
+//! are written back nested when exporting. Links ingested at the top level are
+//! also exported at the top level, even when other links use them.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{Error, Result};
+use crate::{format_links, parse_lino_to_links, LiNo};
+
+/// A link held by a [`LinkStore`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredLink {
+    pub address: usize,
+    pub name: Option<String>,
+    pub values: Vec<usize>,
+}
+
+/// In-memory link store with deduplication and pattern queries.
+#[derive(Debug, Clone, Default)]
+pub struct LinkStore {
+    links: Vec<Option<StoredLink>>,
+    names: HashMap<String, usize>,
+    unnamed: HashMap<Vec<usize>, usize>,
+    roots: HashSet<usize>,
+}
+
+impl LinkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of links in the store.
+    pub fn len(&self) -> usize {
+        self.links.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, address: usize) -> Option<&StoredLink> {
+        address.checked_sub(1).and_then(|slot| self.links.get(slot)).and_then(Option::as_ref)
+    }
+
+    /// All links, in address order.
+    pub fn iter(&self) -> impl Iterator<Item = &StoredLink> {
+        self.links.iter().flatten()
+    }
+
+    pub fn address_of(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    pub fn name_of(&self, address: usize) -> Option<&str> {
+        self.get(address).and_then(|link| link.name.as_deref())
+    }
+
+    fn check(&self, values: &[usize]) -> Result<()> {
+        match values.iter().find(|value| self.get(**value).is_none()) {
+            Some(value) => Err(Error::new(format!("link {} does not exist", value))),
+            None => Ok(()),
+        }
+    }
+
+    fn push(&mut self, name: Option<String>, values: Vec<usize>) -> usize {
+        let address = self.links.len() + 1;
+        match &name {
+            Some(name) => {
+                self.names.insert(name.clone(), address);
+            }
+            None => {
+                self.unnamed.insert(values.clone(), address);
+            }
+        }
+        self.links.push(Some(StoredLink { address, name, values }));
+        address
+    }
+
+    /// Creates an unnamed link, or returns the address of the identical link
+    /// that already exists.
+    pub fn create(&mut self, values: Vec<usize>) -> Result<usize> {
+        self.check(&values)?;
+        if let Some(address) = self.unnamed.get(&values) {
+            return Ok(*address);
+        }
+        Ok(self.push(None, values))
+    }
+
+    /// Creates a named link, or sets the values of the point link created
+    /// for the name by an earlier reference. Defining a name again with the
+    /// same values returns its address; different values are an error.
+    pub fn create_named(&mut self, name: &str, values: Vec<usize>) -> Result<usize> {
+        self.check(&values)?;
+        match self.address_of(name) {
+            None => Ok(self.push(Some(name.to_string()), values)),
+            Some(address) => {
+                let link = self.links[address - 1].as_mut().unwrap();
+                if link.values.is_empty() {
+                    link.values = values;
+                } else if link.values != values {
+                    return Err(Error::new(format!("`{}` is already defined as link {}", name, address)));
+                }
+                Ok(address)
+            }
+        }
+    }
+
+    /// Address of the link named `name`, creating a point link for it if
+    /// there is none.
+    pub fn point(&mut self, name: &str) -> usize {
+        match self.address_of(name) {
+            Some(address) => address,
+            None => self.push(Some(name.to_string()), vec![]),
+        }
+    }
+
+    // Whether `target` is `from` or is reached from it through unnamed links
+    fn reaches(&self, from: usize, target: usize) -> bool {
+        let mut stack = vec![from];
+        let mut seen = vec![false; self.links.len()];
+        while let Some(address) = stack.pop() {
+            if address == target {
+                return true;
+            }
+            if std::mem::replace(&mut seen[address - 1], true) {
+                continue;
+            }
+            let link = self.get(address).unwrap();
+            if link.name.is_none() {
+                stack.extend(&link.values);
+            }
+        }
+        false
+    }
+
+    /// Replaces the values of a link. Unnamed links cannot contain
+    /// themselves, directly or through other unnamed links.
+    pub fn update(&mut self, address: usize, values: Vec<usize>) -> Result<()> {
+        self.check(&values)?;
+        let link = self.get(address).ok_or_else(|| Error::new(format!("link {} does not exist", address)))?;
+        if link.name.is_none() {
+            if values.iter().any(|value| self.reaches(*value, address)) {
+                return Err(Error::new(format!("link {} would contain itself", address)));
+            }
+            if let Some(existing) = self.unnamed.get(&values).filter(|existing| **existing != address) {
+                return Err(Error::new(format!("link {} already has these values", existing)));
+            }
+            let old = link.values.clone();
+            self.unnamed.remove(&old);
+            self.unnamed.insert(values.clone(), address);
+        }
+        self.links[address - 1].as_mut().unwrap().values = values;
+        Ok(())
+    }
+
+    /// Deletes a link that no other link uses.
+    pub fn delete(&mut self, address: usize) -> Result<StoredLink> {
+        if self.get(address).is_none() {
+            return Err(Error::new(format!("link {} does not exist", address)));
+        }
+        if let Some(user) = self.iter().find(|link| link.address != address && link.values.contains(&address)) {
+            return Err(Error::new(format!("link {} is used by link {}", address, user.address)));
+        }
+        let link = self.links[address - 1].take().unwrap();
+        self.roots.remove(&address);
+        match &link.name {
+            Some(name) => self.names.remove(name),
+            None => self.unnamed.remove(&link.values),
+        };
+        Ok(link)
+    }
+
+    /// Addresses of links with as many values as `pattern` whose values match
+    /// it, where `None` matches any value. `[Some(x), None]` finds every
+    /// doublet with source `x`.
+    pub fn query(&self, pattern: &[Option<usize>]) -> Vec<usize> {
+        self.iter()
+            .filter(|link| {
+                link.values.len() == pattern.len()
+                    && link.values.iter().zip(pattern).all(|(value, expected)| expected.is_none_or(|expected| expected == *value))
+            })
+            .map(|link| link.address)
+            .collect()
+    }
+
+    /// Like [`LinkStore::query`], with values given by name. Unknown names
+    /// match nothing.
+    pub fn query_names(&self, pattern: &[Option<&str>]) -> Vec<usize> {
+        let mut addresses = vec![];
+        for value in pattern {
+            match value {
+                Some(name) => match self.address_of(name) {
+                    Some(address) => addresses.push(Some(address)),
+                    None => return vec![],
+                },
+                None => addresses.push(None),
+            }
+        }
+        self.query(&addresses)
+    }
+
+    fn ingest_value(&mut self, lino: &LiNo<String>) -> Result<usize> {
+        match lino {
+            LiNo::Ref(name) => Ok(self.point(name)),
+            LiNo::Link { id, values } => {
+                let mut addresses = Vec::with_capacity(values.len());
+                for value in values {
+                    addresses.push(self.ingest_value(value)?);
+                }
+                match id {
+                    Some(name) => self.create_named(name, addresses),
+                    None => self.create(addresses),
+                }
+            }
+        }
+    }
+
+    /// Stores links, returning the addresses of the top-level ones.
+    pub fn ingest(&mut self, links: &[LiNo<String>]) -> Result<Vec<usize>> {
+        let mut addresses = Vec::with_capacity(links.len());
+        for link in links {
+            let address = self.ingest_value(link)?;
+            self.add_root(address);
+            addresses.push(address);
+        }
+        Ok(addresses)
+    }
+
+    // Marks a link as written at the top level, so exporting keeps it there
+    pub(crate) fn add_root(&mut self, address: usize) {
+        self.roots.insert(address);
+    }
+
+    /// Parses a document and stores its links.
+    pub fn ingest_document(&mut self, document: &str) -> Result<Vec<usize>> {
+        self.ingest(&parse_lino_to_links(document).map_err(Error::new)?)
+    }
+
+    fn export_value(&self, address: usize) -> LiNo<String> {
+        let link = self.get(address).unwrap();
+        match &link.name {
+            Some(name) => LiNo::Ref(name.clone()),
+            None => LiNo::Link {
+                id: None,
+                values: link.values.iter().map(|value| self.export_value(*value)).collect(),
+            },
+        }
+    }
+
+    /// Converts the store back into links. Named links are written with their
+    /// values, points and unnamed links when nothing uses them or when they
+    /// were ingested at the top level.
+    pub fn to_lino(&self) -> Vec<LiNo<String>> {
+        let mut used = vec![false; self.links.len()];
+        for link in self.iter() {
+            for value in &link.values {
+                used[value - 1] = true;
+            }
+        }
+        self.iter()
+            .filter(|link| {
+                let top = !used[link.address - 1] || self.roots.contains(&link.address);
+                match link.name {
+                    Some(_) => !link.values.is_empty() || top,
+                    None => top,
+                }
+            })
+            .map(|link| match &link.name {
+                Some(name) if link.values.is_empty() => LiNo::Ref(name.clone()),
+                Some(name) => LiNo::Link {
+                    id: Some(name.clone()),
+                    values: link.values.iter().map(|value| self.export_value(*value)).collect(),
+                },
+                None => self.export_value(link.address),
+            })
+            .collect()
+    }
+
+    /// Writes the store as a Links Notation document, one link per line.
+    pub fn to_text(&self) -> String {
+        format_links(&self.to_lino(), false)
+    }
+}
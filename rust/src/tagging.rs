@@ -0,0 +1,67 @@
+//! Shapes shared by the conversions to and from JSON, YAML and XML: the tags
+//! marking strings and arrays that would otherwise read back differently,
+//! and documents holding a single converted value.
+
+// With only XML enabled, the tags are unused
+#![cfg_attr(not(any(feature = "json", feature = "yaml")), allow(dead_code))]
+
+use crate::error::{Error, Result};
+use crate::{check_representable, format_links, parse_lino_to_links, LiNo};
+
+pub(crate) const STRING_TAG: &str = "string";
+pub(crate) const ARRAY_TAG: &str = "array";
+
+pub(crate) fn empty_link() -> LiNo<String> {
+    LiNo::Link { id: None, values: vec![] }
+}
+
+pub(crate) fn tagged(tag: &str, values: Vec<LiNo<String>>) -> LiNo<String> {
+    LiNo::Link {
+        id: Some(tag.to_string()),
+        values: if values.is_empty() { vec![empty_link()] } else { values },
+    }
+}
+
+pub(crate) fn is_member(value: &LiNo<String>) -> bool {
+    matches!(value, LiNo::Link { id: Some(_), values } if values.len() == 1)
+}
+
+// Wraps array elements, tagging them when an untagged link would read back differently
+pub(crate) fn array(values: Vec<LiNo<String>>) -> LiNo<String> {
+    let ambiguous = match values.as_slice() {
+        [] | [LiNo::Ref(_)] => true,
+        values => values.iter().all(is_member),
+    };
+    if ambiguous {
+        tagged(ARRAY_TAG, values)
+    } else {
+        LiNo::Link { id: None, values }
+    }
+}
+
+// Returns the elements of a tagged array, treating the `()` placeholder as no elements
+pub(crate) fn tagged_elements(values: &[LiNo<String>]) -> &[LiNo<String>] {
+    match values {
+        [LiNo::Link { id: None, values: empty }] if empty.is_empty() => &[],
+        values => values,
+    }
+}
+
+// Writes a converted value as a document, one member or element per line
+pub(crate) fn format_document(lino: LiNo<String>) -> Result<String> {
+    check_representable(&lino)?;
+    Ok(match lino {
+        LiNo::Link { id: None, values } if values.len() > 1 => format_links(&values, true),
+        lino => lino.format(true),
+    })
+}
+
+// Reads a document written by `format_document` back as a single value
+pub(crate) fn parse_document(document: &str) -> Result<LiNo<String>> {
+    let mut links = parse_lino_to_links(document).map_err(Error::new)?;
+    match links.len() {
+        0 => Err(Error::new("empty document")),
+        1 => Ok(links.remove(0)),
+        _ => Ok(LiNo::Link { id: None, values: links }),
+    }
+}
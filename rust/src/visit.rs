@@ -0,0 +1,244 @@
+//! Traversal of links: visitors, folds and iterators.
+//!
+//! [`Visitor`] and [`VisitorMut`] walk a link and its values depth-first,
+//! calling a method for every link, id and reference; each method defaults to
+//! walking further, so implementations only override what they need and call
+//! the matching `walk_*` function to keep descending. [`Fold`] rebuilds a link
+//! bottom-up into any output, such as a `LiNo<U>` or a summary value.
+//!
+//! The iterators yield every link and reference with its depth and path. The
+//! path lists the position of each value on the way down: from
+//! [`LiNo::pre_order`] the link itself has an empty path, and from
+//! [`pre_order`] a top-level link has its index as path.
+
+use std::collections::VecDeque;
+
+use crate::LiNo;
+
+/// Read-only visitor over links.
+pub trait Visitor<T> {
+    fn visit_lino(&mut self, lino: &LiNo<T>) {
+        walk_lino(self, lino);
+    }
+
+    fn visit_link(&mut self, id: Option<&T>, values: &[LiNo<T>]) {
+        walk_link(self, id, values);
+    }
+
+    fn visit_id(&mut self, _id: &T) {}
+
+    fn visit_ref(&mut self, _value: &T) {}
+}
+
+pub fn walk_lino<T, V: Visitor<T> + ?Sized>(visitor: &mut V, lino: &LiNo<T>) {
+    match lino {
+        LiNo::Ref(value) => visitor.visit_ref(value),
+        LiNo::Link { id, values } => visitor.visit_link(id.as_ref(), values),
+    }
+}
+
+pub fn walk_link<T, V: Visitor<T> + ?Sized>(visitor: &mut V, id: Option<&T>, values: &[LiNo<T>]) {
+    if let Some(id) = id {
+        visitor.visit_id(id);
+    }
+    for value in values {
+        visitor.visit_lino(value);
+    }
+}
+
+/// Visitor that can change links in place.
+pub trait VisitorMut<T> {
+    fn visit_lino_mut(&mut self, lino: &mut LiNo<T>) {
+        walk_lino_mut(self, lino);
+    }
+
+    fn visit_link_mut(&mut self, id: &mut Option<T>, values: &mut Vec<LiNo<T>>) {
+        walk_link_mut(self, id, values);
+    }
+
+    fn visit_id_mut(&mut self, _id: &mut T) {}
+
+    fn visit_ref_mut(&mut self, _value: &mut T) {}
+}
+
+pub fn walk_lino_mut<T, V: VisitorMut<T> + ?Sized>(visitor: &mut V, lino: &mut LiNo<T>) {
+    match lino {
+        LiNo::Ref(value) => visitor.visit_ref_mut(value),
+        LiNo::Link { id, values } => visitor.visit_link_mut(id, values),
+    }
+}
+
+pub fn walk_link_mut<T, V: VisitorMut<T> + ?Sized>(visitor: &mut V, id: &mut Option<T>, values: &mut Vec<LiNo<T>>) {
+    if let Some(id) = id {
+        visitor.visit_id_mut(id);
+    }
+    for value in values {
+        visitor.visit_lino_mut(value);
+    }
+}
+
+/// Bottom-up transformation of links into `Output`, with the values of a link
+/// folded before the link itself.
+pub trait Fold<T> {
+    type Output;
+
+    fn fold_ref(&mut self, value: T) -> Self::Output;
+
+    fn fold_link(&mut self, id: Option<T>, values: Vec<Self::Output>) -> Self::Output;
+}
+
+/// A link or reference reached by a traversal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node<'a, T> {
+    pub lino: &'a LiNo<T>,
+    pub depth: usize,
+    pub path: Vec<usize>,
+}
+
+impl<'a, T> Node<'a, T> {
+    // Nodes for the values of this one, in order
+    fn children(&self) -> impl DoubleEndedIterator<Item = Node<'a, T>> + '_ {
+        let values = match self.lino {
+            LiNo::Link { values, .. } => values.as_slice(),
+            LiNo::Ref(_) => &[],
+        };
+        values.iter().enumerate().map(|(index, lino)| {
+            let mut path = self.path.clone();
+            path.push(index);
+            Node {
+                lino,
+                depth: self.depth + 1,
+                path,
+            }
+        })
+    }
+}
+
+fn roots<T>(links: &[LiNo<T>]) -> impl DoubleEndedIterator<Item = Node<'_, T>> {
+    links.iter().enumerate().map(|(index, lino)| Node {
+        lino,
+        depth: 0,
+        path: vec![index],
+    })
+}
+
+/// Pre-order iterator: every link before its values.
+pub struct PreOrder<'a, T> {
+    stack: Vec<Node<'a, T>>,
+}
+
+impl<'a, T> Iterator for PreOrder<'a, T> {
+    type Item = Node<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let children: Vec<_> = node.children().rev().collect();
+        self.stack.extend(children);
+        Some(node)
+    }
+}
+
+/// Post-order iterator: every link after its values.
+pub struct PostOrder<'a, T> {
+    // Nodes with whether their values were already pushed
+    stack: Vec<(Node<'a, T>, bool)>,
+}
+
+impl<'a, T> Iterator for PostOrder<'a, T> {
+    type Item = Node<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(node);
+            }
+            let children: Vec<_> = node.children().rev().map(|child| (child, false)).collect();
+            self.stack.push((node, true));
+            self.stack.extend(children);
+        }
+    }
+}
+
+/// Breadth-first iterator: links by increasing depth.
+pub struct BreadthFirst<'a, T> {
+    queue: VecDeque<Node<'a, T>>,
+}
+
+impl<'a, T> Iterator for BreadthFirst<'a, T> {
+    type Item = Node<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        let children: Vec<_> = node.children().collect();
+        self.queue.extend(children);
+        Some(node)
+    }
+}
+
+fn root<T>(lino: &LiNo<T>) -> Node<'_, T> {
+    Node {
+        lino,
+        depth: 0,
+        path: vec![],
+    }
+}
+
+/// Pre-order traversal of a list of top-level links.
+pub fn pre_order<T>(links: &[LiNo<T>]) -> PreOrder<'_, T> {
+    PreOrder {
+        stack: roots(links).rev().collect(),
+    }
+}
+
+/// Post-order traversal of a list of top-level links.
+pub fn post_order<T>(links: &[LiNo<T>]) -> PostOrder<'_, T> {
+    PostOrder {
+        stack: roots(links).rev().map(|node| (node, false)).collect(),
+    }
+}
+
+/// Breadth-first traversal of a list of top-level links.
+pub fn breadth_first<T>(links: &[LiNo<T>]) -> BreadthFirst<'_, T> {
+    BreadthFirst { queue: roots(links).collect() }
+}
+
+impl<T> LiNo<T> {
+    pub fn accept<V: Visitor<T> + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_lino(self);
+    }
+
+    pub fn accept_mut<V: VisitorMut<T> + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_lino_mut(self);
+    }
+
+    /// Folds the link bottom-up.
+    pub fn fold<F: Fold<T> + ?Sized>(self, folder: &mut F) -> F::Output {
+        match self {
+            LiNo::Ref(value) => folder.fold_ref(value),
+            LiNo::Link { id, values } => {
+                let values = values.into_iter().map(|value| value.fold(folder)).collect();
+                folder.fold_link(id, values)
+            }
+        }
+    }
+
+    /// The link and all of its values, each link before its values.
+    pub fn pre_order(&self) -> PreOrder<'_, T> {
+        PreOrder { stack: vec![root(self)] }
+    }
+
+    /// The link and all of its values, each link after its values.
+    pub fn post_order(&self) -> PostOrder<'_, T> {
+        PostOrder {
+            stack: vec![(root(self), false)],
+        }
+    }
+
+    /// The link and all of its values, by increasing depth.
+    pub fn breadth_first(&self) -> BreadthFirst<'_, T> {
+        BreadthFirst {
+            queue: VecDeque::from([root(self)]),
+        }
+    }
+}
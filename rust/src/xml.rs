@@ -0,0 +1,274 @@
+//! Conversion between XML and Links Notation.
+//!
+//! XML elements are mapped onto `LiNo<String>` as follows:
+//!
+//! | XML                                 | Links Notation                  |
+//! |-------------------------------------|---------------------------------|
+//! | `<title>Hello</title>`              | `(title: Hello)`                |
+//! | `<img src="a.png" alt="A"/>`        | `(img: (@src: a.png) (@alt: A))` |
+//! | `<p>Hi <b>there</b>!</p>`           | `(p: Hi (b: there) !)`          |
+//! | `<br/>`, `<a href=""/>`             | `(br: ())`, `(a: (@href: ()))`  |
+//! | `<person id="john">…</person>`      | `(john: (person: …))`           |
+//! | `<person friend-ref="jane"/>`       | `(person: (@friend-ref: jane))` |
+//! | `<group members-refs="a b"/>`       | `(group: (@members-refs: a b))` |
+//!
+//! An element becomes a link with its tag as the id. Attributes come first as
+//! `(@name: value)` links, followed by child elements and text in document
+//! order. Text is trimmed and whitespace-only text is dropped; comments,
+//! processing instructions and the XML declaration are not kept.
+//!
+//! An element with an `id` attribute is wrapped in a link named after that id,
+//! so the element can be referred to by name. Attribute values become
+//! references, which makes `IDREF` values such as `friend-ref="jane"` refer to
+//! the element with `id="jane"`. Values of `idrefs` attributes and attributes
+//! ending with `-refs` are split into one reference per id.
+//!
+//! A link with a single element link as its only value is read as an element
+//! with an id, so an element whose only content is a child element is written
+//! with a leading `()`: `<list><item/></list>` becomes `(list: () (item: ()))`.
+
+use quick_xml::escape::{escape, partial_escape};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::error::{Error, Result};
+use crate::tagging::{empty_link, format_document, parse_document};
+use crate::LiNo;
+
+const ID_ATTRIBUTE: &str = "id";
+const ATTRIBUTE_PREFIX: &str = "@";
+
+fn is_idrefs(name: &str) -> bool {
+    name == "idrefs" || name.ends_with("-refs")
+}
+
+fn is_attribute(name: &str) -> bool {
+    name.starts_with(ATTRIBUTE_PREFIX)
+}
+
+fn is_element(value: &LiNo<String>) -> bool {
+    matches!(value, LiNo::Link { id: Some(tag), .. } if !is_attribute(tag))
+}
+
+struct Element {
+    tag: String,
+    id: Option<String>,
+    content: Vec<LiNo<String>>,
+    text: String,
+}
+
+impl Element {
+    fn new(start: &BytesStart) -> std::result::Result<Self, quick_xml::Error> {
+        let mut element = Element {
+            tag: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+            id: None,
+            content: vec![],
+            text: String::new(),
+        };
+        for attribute in start.attributes() {
+            let attribute = attribute?;
+            let name = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+            let value = attribute.unescape_value()?.into_owned();
+            if name == ID_ATTRIBUTE {
+                element.id = Some(value);
+                continue;
+            }
+            let values = if value.is_empty() {
+                vec![empty_link()]
+            } else if is_idrefs(&name) {
+                value.split_whitespace().map(|id| LiNo::Ref(id.to_string())).collect()
+            } else {
+                vec![LiNo::Ref(value)]
+            };
+            element.content.push(LiNo::Link {
+                id: Some(format!("{}{}", ATTRIBUTE_PREFIX, name)),
+                values,
+            });
+        }
+        Ok(element)
+    }
+
+    fn flush_text(&mut self) {
+        let text = self.text.trim();
+        if !text.is_empty() {
+            self.content.push(LiNo::Ref(text.to_string()));
+        }
+        self.text.clear();
+    }
+
+    fn finish(mut self) -> LiNo<String> {
+        self.flush_text();
+        let mut values = self.content;
+        if values.is_empty() || (values.len() == 1 && is_element(&values[0])) {
+            values.insert(0, empty_link());
+        }
+        let element = LiNo::Link {
+            id: Some(self.tag),
+            values,
+        };
+        match self.id {
+            Some(id) => LiNo::Link {
+                id: Some(id),
+                values: vec![element],
+            },
+            None => element,
+        }
+    }
+}
+
+/// Converts an XML document into a `LiNo<String>` link for its root element.
+pub fn xml_to_lino(xml: &str) -> Result<LiNo<String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Element> = vec![];
+    let mut root = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|error| Error::new(error.to_string()).at(xml, reader.error_position() as usize))?;
+        let offset = reader.buffer_position() as usize;
+        let node = match event {
+            Event::Start(start) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent.flush_text();
+                }
+                let element = Element::new(&start).map_err(|error| Error::new(error.to_string()).at(xml, offset))?;
+                stack.push(element);
+                continue;
+            }
+            Event::Empty(start) => {
+                if let Some(parent) = stack.last_mut() {
+                    parent.flush_text();
+                }
+                Element::new(&start)
+                    .map_err(|error| Error::new(error.to_string()).at(xml, offset))?
+                    .finish()
+            }
+            Event::End(_) => match stack.pop() {
+                Some(element) => element.finish(),
+                None => return Err(Error::new("unexpected closing tag").at(xml, offset)),
+            },
+            Event::Text(text) => {
+                let text = text.unescape().map_err(|error| Error::new(error.to_string()).at(xml, offset))?;
+                match stack.last_mut() {
+                    Some(element) => element.text.push_str(&text),
+                    None if text.trim().is_empty() => {}
+                    None => return Err(Error::new("text outside of the root element").at(xml, offset)),
+                }
+                continue;
+            }
+            Event::CData(data) => {
+                let data = data.decode().map_err(|error| Error::new(error.to_string()).at(xml, offset))?;
+                match stack.last_mut() {
+                    Some(element) => element.text.push_str(&data),
+                    None => return Err(Error::new("text outside of the root element").at(xml, offset)),
+                }
+                continue;
+            }
+            Event::Eof => break,
+            _ => continue,
+        };
+        match stack.last_mut() {
+            Some(parent) => parent.content.push(node),
+            None if root.is_none() => root = Some(node),
+            None => return Err(Error::new("XML document must have a single root element").at(xml, offset)),
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(Error::new("unexpected end of XML document").at(xml, xml.len()));
+    }
+    root.ok_or_else(|| Error::new("XML document has no root element"))
+}
+
+fn check_name(name: &str) -> Result<&str> {
+    let valid = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.')
+        && !name.contains(|c: char| c.is_whitespace() || "<>&\"'=/@!?".contains(c));
+    if valid {
+        Ok(name)
+    } else {
+        Err(Error::new(format!("`{}` is not a valid XML name", name)))
+    }
+}
+
+fn write_element(output: &mut String, lino: &LiNo<String>) -> Result<()> {
+    let (tag, id, values) = match lino {
+        LiNo::Link { id: Some(id), values } => match values.as_slice() {
+            [LiNo::Link { id: Some(tag), values }] if !is_attribute(tag) => (tag, Some(id), values),
+            _ => (id, None, values),
+        },
+        LiNo::Link { id: None, .. } => return Err(Error::new("element links must have a tag as their id")),
+        LiNo::Ref(text) => return Err(Error::new(format!("expected an element, found text `{}`", text))),
+    };
+
+    output.push('<');
+    output.push_str(check_name(tag)?);
+    if let Some(id) = id {
+        output.push_str(&format!(" {}=\"{}\"", ID_ATTRIBUTE, escape(id.as_str())));
+    }
+    for value in values {
+        match value {
+            LiNo::Link { id: Some(name), values } => {
+                if let Some(name) = name.strip_prefix(ATTRIBUTE_PREFIX) {
+                    let mut parts = vec![];
+                    for value in values {
+                        match value {
+                            LiNo::Ref(part) => parts.push(part.as_str()),
+                            empty if *empty == empty_link() => {}
+                            _ => return Err(Error::new(format!("attribute `{}` must only contain references", name))),
+                        }
+                    }
+                    output.push_str(&format!(" {}=\"{}\"", check_name(name)?, escape(parts.join(" "))));
+                }
+            }
+            // The `()` placeholder of empty elements and single children
+            LiNo::Link { id: None, values: empty } if empty.is_empty() => {}
+            LiNo::Link { id: None, .. } => {
+                return Err(Error::new(format!(
+                    "`{}` in element `{}` is not text, an element or an attribute",
+                    value, tag
+                )))
+            }
+            LiNo::Ref(_) => {}
+        }
+    }
+
+    let content: Vec<_> = values
+        .iter()
+        .filter(|value| matches!(value, LiNo::Ref(_)) || is_element(value))
+        .collect();
+    if content.is_empty() {
+        output.push_str("/>");
+        return Ok(());
+    }
+    output.push('>');
+    for value in content {
+        match value {
+            LiNo::Ref(text) => output.push_str(&partial_escape(text.as_str())),
+            element => write_element(output, element)?,
+        }
+    }
+    output.push_str(&format!("</{}>", tag));
+    Ok(())
+}
+
+/// Converts a `LiNo<String>` link in the shape produced by [`xml_to_lino`]
+/// into an XML document. Values with no XML form, such as unnamed links
+/// other than `()`, are an error rather than being dropped.
+pub fn lino_to_xml(lino: &LiNo<String>) -> Result<String> {
+    let mut output = String::new();
+    write_element(&mut output, lino)?;
+    Ok(output)
+}
+
+/// Converts an XML document into a Links Notation document.
+pub fn xml_to_text(xml: &str) -> Result<String> {
+    format_document(xml_to_lino(xml)?)
+}
+
+/// Converts a Links Notation document in the shape produced by
+/// [`xml_to_text`] into an XML document.
+pub fn text_to_xml(document: &str) -> Result<String> {
+    lino_to_xml(&parse_document(document)?)
+}
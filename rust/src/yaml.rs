@@ -0,0 +1,391 @@
+//! Conversion between YAML and Links Notation.
+//!
+//! YAML nodes use the same shape as the `json` module:
+//! mappings become links of `(key: value)` links, sequences become links of
+//! their elements and scalars become references. Plain scalars are kept as
+//! written, so `~`, `True` or `0x1F` keep their YAML meaning, while quoted
+//! scalars that would read back as something else are tagged with `string`.
+//!
+//! Anchors and aliases map onto link ids and references. An anchored node is
+//! wrapped in a link named after the anchor, and every alias becomes a
+//! reference to that name, so shared nodes stay shared:
+//!
+//! ```yaml
+//! base: &defaults
+//!   replicas: 2
+//! web: *defaults
+//! ```
+//!
+//! ```text
+//! base: (defaults: ((replicas: 2)))
+//! web: defaults
+//! ```
+//!
+//! Mapping keys cannot be wrapped in a link, so aliases of an anchored key
+//! become the key itself. Strings equal to an anchor name are tagged with
+//! `string`, so they are not mistaken for aliases. Anchors named `string` or
+//! `array`, and anchors that are redefined later in the document, get a
+//! numeric suffix to keep ids unique. Only single-document streams with scalar
+//! mapping keys are supported.
+
+use std::collections::{HashMap, HashSet};
+
+use yaml_rust2::parser::{Event, Parser};
+use yaml_rust2::scanner::{Marker, ScanError, Scanner, TScalarStyle, TokenType};
+use yaml_rust2::Yaml;
+
+use crate::error::{Error, Result};
+use crate::tagging::{
+    array, empty_link, format_document, is_member, parse_document, tagged, tagged_elements, ARRAY_TAG,
+    STRING_TAG,
+};
+use crate::LiNo;
+
+fn scan_error(error: ScanError) -> Error {
+    let marker = error.marker();
+    Error::new(error.info().to_string()).with_location(marker.line(), marker.col() + 1)
+}
+
+fn is_literal(scalar: &str) -> bool {
+    !matches!(Yaml::from_str(scalar), Yaml::String(_))
+}
+
+// Anchor names indexed by the ids the parser assigns to them, in order of appearance
+fn anchor_names(yaml: &str) -> Result<HashMap<usize, String>> {
+    let mut scanner = Scanner::new(yaml.chars());
+    let mut names = HashMap::new();
+    let mut used = HashSet::new();
+    for token in scanner.by_ref() {
+        if let TokenType::Anchor(name) = token.1 {
+            let mut unique = name.clone();
+            let mut suffix = 2;
+            while unique == STRING_TAG || unique == ARRAY_TAG || used.contains(&unique) {
+                unique = format!("{}_{}", name, suffix);
+                suffix += 1;
+            }
+            used.insert(unique.clone());
+            names.insert(names.len() + 1, unique);
+        }
+    }
+    match scanner.get_error() {
+        Some(error) => Err(scan_error(error)),
+        None => Ok(names),
+    }
+}
+
+struct Importer<'a> {
+    parser: Parser<std::str::Chars<'a>>,
+    anchors: HashMap<usize, String>,
+    // Values of anchored mapping keys, which cannot carry a link id
+    key_anchors: HashMap<usize, String>,
+    names: HashSet<String>,
+}
+
+impl Importer<'_> {
+    fn next(&mut self) -> Result<(Event, Marker)> {
+        self.parser.next_token().map_err(scan_error)
+    }
+
+    fn string(&self, value: String) -> LiNo<String> {
+        if value.is_empty() {
+            tagged(STRING_TAG, vec![])
+        } else if is_literal(&value) || self.names.contains(&value) {
+            tagged(STRING_TAG, vec![LiNo::Ref(value)])
+        } else {
+            LiNo::Ref(value)
+        }
+    }
+
+    fn anchored(&self, anchor: usize, node: LiNo<String>) -> LiNo<String> {
+        match self.anchors.get(&anchor) {
+            Some(name) => LiNo::Link {
+                id: Some(name.clone()),
+                values: vec![node],
+            },
+            None => node,
+        }
+    }
+
+    fn node(&mut self, event: Event, marker: Marker) -> Result<LiNo<String>> {
+        match event {
+            Event::Alias(anchor) => match (self.key_anchors.get(&anchor), self.anchors.get(&anchor)) {
+                (Some(key), _) => Ok(self.string(key.clone())),
+                (None, Some(name)) => Ok(LiNo::Ref(name.clone())),
+                (None, None) => {
+                    Err(Error::new("alias to an unknown anchor").with_location(marker.line(), marker.col() + 1))
+                }
+            },
+            Event::Scalar(value, style, anchor, _) => {
+                let node = match style {
+                    TScalarStyle::Plain if value.is_empty() => LiNo::Ref("null".to_string()),
+                    TScalarStyle::Plain if is_literal(&value) => LiNo::Ref(value),
+                    _ => self.string(value),
+                };
+                Ok(self.anchored(anchor, node))
+            }
+            Event::SequenceStart(anchor, _) => {
+                let mut elements = vec![];
+                loop {
+                    match self.next()? {
+                        (Event::SequenceEnd, _) => break,
+                        (event, marker) => elements.push(self.node(event, marker)?),
+                    }
+                }
+                Ok(self.anchored(anchor, array(elements)))
+            }
+            Event::MappingStart(anchor, _) => {
+                let mut members = vec![];
+                loop {
+                    let key = match self.next()? {
+                        (Event::MappingEnd, _) => break,
+                        (Event::Scalar(key, _, anchor, _), _) => {
+                            if anchor != 0 {
+                                self.key_anchors.insert(anchor, key.clone());
+                            }
+                            key
+                        }
+                        (_, marker) => {
+                            return Err(Error::new("only scalar mapping keys are supported")
+                                .with_location(marker.line(), marker.col() + 1))
+                        }
+                    };
+                    let (event, marker) = self.next()?;
+                    members.push(LiNo::Link {
+                        id: Some(key),
+                        values: vec![self.node(event, marker)?],
+                    });
+                }
+                Ok(self.anchored(anchor, LiNo::Link { id: None, values: members }))
+            }
+            _ => Err(Error::new("unexpected YAML event").with_location(marker.line(), marker.col() + 1)),
+        }
+    }
+}
+
+/// Converts a single YAML document into a `LiNo<String>` link.
+pub fn yaml_to_lino(yaml: &str) -> Result<LiNo<String>> {
+    let anchors = anchor_names(yaml)?;
+    let names = anchors.values().cloned().collect();
+    let mut importer = Importer {
+        parser: Parser::new_from_str(yaml),
+        anchors,
+        key_anchors: HashMap::new(),
+        names,
+    };
+
+    let mut document = None;
+    loop {
+        match importer.next()? {
+            (Event::StreamEnd, _) => break,
+            (Event::StreamStart, _) | (Event::DocumentStart, _) | (Event::DocumentEnd, _) => {}
+            (_, marker) if document.is_some() => {
+                return Err(Error::new("multiple YAML documents are not supported")
+                    .with_location(marker.line(), marker.col() + 1))
+            }
+            (event, marker) => document = Some(importer.node(event, marker)?),
+        }
+    }
+    Ok(document.unwrap_or_else(|| LiNo::Ref("null".to_string())))
+}
+
+fn is_plain_safe(scalar: &str) -> bool {
+    let allowed = |c: char| c.is_alphanumeric() || " _./~+-()".contains(c);
+    match scalar.chars().next() {
+        Some(first) if first.is_alphanumeric() || "_./~+".contains(first) => {
+            scalar.chars().all(allowed) && !scalar.ends_with(' ')
+        }
+        Some('-') => scalar.len() > 1 && !scalar.starts_with("- ") && scalar.chars().all(allowed),
+        _ => false,
+    }
+}
+
+fn quoted(scalar: &str) -> String {
+    let mut result = String::from("\"");
+    for c in scalar.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04X}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn key(key: &str) -> String {
+    if is_plain_safe(key) && !is_literal(key) {
+        key.to_string()
+    } else {
+        quoted(key)
+    }
+}
+
+enum Node<'a> {
+    Scalar(String),
+    Alias(&'a str),
+    Sequence(&'a [LiNo<String>]),
+    Mapping(&'a [LiNo<String>]),
+}
+
+struct Exporter<'a> {
+    anchors: HashSet<&'a str>,
+    defined: HashSet<&'a str>,
+    output: String,
+}
+
+impl<'a> Exporter<'a> {
+    // Anchors are the ids of links that wrap a single node outside of a mapping
+    fn collect_anchors(&mut self, lino: &'a LiNo<String>) {
+        if let LiNo::Link { id, values } = lino {
+            let members = id.is_none() && values.iter().all(is_member);
+            match id.as_deref() {
+                Some(tag) if tag == STRING_TAG => return,
+                Some(tag) if tag != ARRAY_TAG && values.len() == 1 => {
+                    self.anchors.insert(tag);
+                }
+                _ => {}
+            }
+            for value in values {
+                match value {
+                    LiNo::Link { values: member, .. } if members => self.collect_anchors(&member[0]),
+                    value => self.collect_anchors(value),
+                }
+            }
+        }
+    }
+
+    fn node(&mut self, lino: &'a LiNo<String>) -> Result<(Option<&'a str>, Node<'a>)> {
+        Ok(match lino {
+            LiNo::Ref(value) if self.anchors.contains(value.as_str()) => {
+                if !self.defined.contains(value.as_str()) {
+                    return Err(Error::new(format!(
+                        "reference `{}` appears before the link it refers to",
+                        value
+                    )));
+                }
+                (None, Node::Alias(value))
+            }
+            LiNo::Ref(value) if is_plain_safe(value) => (None, Node::Scalar(value.clone())),
+            LiNo::Ref(value) => (None, Node::Scalar(quoted(value))),
+            LiNo::Link { id: Some(tag), values } if tag == STRING_TAG => match values.as_slice() {
+                [LiNo::Ref(value)] => (None, Node::Scalar(quoted(value))),
+                [empty] if *empty == empty_link() => (None, Node::Scalar(quoted(""))),
+                _ => return Err(Error::new("`string` link must contain a single reference or `()`")),
+            },
+            LiNo::Link { id: Some(tag), values } if tag == ARRAY_TAG => (None, Node::Sequence(tagged_elements(values))),
+            LiNo::Link { id: Some(anchor), values } if values.len() == 1 => {
+                self.defined.insert(anchor);
+                let (_, node) = self.node(&values[0])?;
+                (Some(anchor.as_str()), node)
+            }
+            LiNo::Link { id: Some(id), .. } => {
+                return Err(Error::new(format!("link `{}` must wrap exactly one node", id)))
+            }
+            LiNo::Link { id: None, values } if values.iter().all(is_member) => (None, Node::Mapping(values)),
+            LiNo::Link { id: None, values } => (None, Node::Sequence(values)),
+        })
+    }
+
+    // Writes the node after a `key:` or `-` indicator that has already been written
+    fn write_value(&mut self, lino: &'a LiNo<String>, indent: usize, in_sequence: bool) -> Result<()> {
+        let (anchor, node) = self.node(lino)?;
+        if let Some(anchor) = anchor {
+            self.output.push_str(&format!(" &{}", anchor));
+        }
+        match node {
+            Node::Scalar(scalar) => self.output.push_str(&format!(" {}\n", scalar)),
+            Node::Alias(alias) => self.output.push_str(&format!(" *{}\n", alias)),
+            Node::Sequence([]) => self.output.push_str(" []\n"),
+            Node::Mapping([]) => self.output.push_str(" {}\n"),
+            Node::Sequence(elements) if in_sequence && anchor.is_none() => {
+                self.output.push(' ');
+                self.write_sequence(elements, indent + 2, true)?;
+            }
+            Node::Mapping(members) if in_sequence && anchor.is_none() => {
+                self.output.push(' ');
+                self.write_mapping(members, indent + 2, true)?;
+            }
+            Node::Sequence(elements) => {
+                self.output.push('\n');
+                self.write_sequence(elements, indent + 2, false)?;
+            }
+            Node::Mapping(members) => {
+                self.output.push('\n');
+                self.write_mapping(members, indent + 2, false)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_sequence(&mut self, elements: &'a [LiNo<String>], indent: usize, inline_first: bool) -> Result<()> {
+        for (index, element) in elements.iter().enumerate() {
+            if index > 0 || !inline_first {
+                self.output.push_str(&" ".repeat(indent));
+            }
+            self.output.push('-');
+            self.write_value(element, indent, true)?;
+        }
+        Ok(())
+    }
+
+    fn write_mapping(&mut self, members: &'a [LiNo<String>], indent: usize, inline_first: bool) -> Result<()> {
+        for (index, member) in members.iter().enumerate() {
+            if let LiNo::Link { id: Some(id), values } = member {
+                if index > 0 || !inline_first {
+                    self.output.push_str(&" ".repeat(indent));
+                }
+                self.output.push_str(&format!("{}:", key(id)));
+                self.write_value(&values[0], indent, false)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Converts a `LiNo<String>` link in the shape produced by [`yaml_to_lino`]
+/// into a YAML document.
+pub fn lino_to_yaml(lino: &LiNo<String>) -> Result<String> {
+    let mut exporter = Exporter {
+        anchors: HashSet::new(),
+        defined: HashSet::new(),
+        output: String::new(),
+    };
+    exporter.collect_anchors(lino);
+
+    let (anchor, node) = exporter.node(lino)?;
+    let prefix = anchor.map(|anchor| format!("&{} ", anchor)).unwrap_or_default();
+    match node {
+        Node::Scalar(scalar) => exporter.output.push_str(&format!("{}{}\n", prefix, scalar)),
+        Node::Alias(alias) => exporter.output.push_str(&format!("*{}\n", alias)),
+        Node::Sequence([]) => exporter.output.push_str(&format!("{}[]\n", prefix)),
+        Node::Mapping([]) => exporter.output.push_str(&format!("{}{{}}\n", prefix)),
+        Node::Sequence(elements) => {
+            if anchor.is_some() {
+                exporter.output.push_str(&format!("{}\n", prefix.trim_end()));
+            }
+            exporter.write_sequence(elements, 0, false)?;
+        }
+        Node::Mapping(members) => {
+            if anchor.is_some() {
+                exporter.output.push_str(&format!("{}\n", prefix.trim_end()));
+            }
+            exporter.write_mapping(members, 0, false)?;
+        }
+    }
+    Ok(exporter.output)
+}
+
+/// Converts a YAML document into a Links Notation document.
+pub fn yaml_to_text(yaml: &str) -> Result<String> {
+    format_document(yaml_to_lino(yaml)?)
+}
+
+/// Converts a Links Notation document in the shape produced by
+/// [`yaml_to_text`] into a YAML document.
+pub fn text_to_yaml(document: &str) -> Result<String> {
+    lino_to_yaml(&parse_document(document)?)
+}
@@ -0,0 +1,61 @@
+use links_notation::graph::LinkGraph;
+
+fn graph(document: &str) -> LinkGraph {
+    LinkGraph::parse(document).unwrap()
+}
+
+#[test]
+fn test_acyclic_graph() {
+    let graph = graph("(a: b c)\n(b: c)\n(c: x)");
+    assert!(!graph.has_cycle());
+    assert!(graph.cycles().is_empty());
+    assert_eq!(graph.strongly_connected_components(), vec![vec![0], vec![1], vec![2]]);
+    assert_eq!(graph.topological_order(), Ok(vec![0, 1, 2]));
+}
+
+#[test]
+fn test_cycles_and_components() {
+    let graph = graph("(a: b)\n(b: c)\n(c: a)\n(d: a)\n(e: e)");
+    assert!(graph.has_cycle());
+    assert_eq!(graph.strongly_connected_components(), vec![vec![0, 1, 2], vec![3], vec![4]]);
+    assert_eq!(graph.cycles(), vec![vec![0, 1, 2], vec![4]]);
+    assert_eq!(graph.topological_order(), Err(vec![vec![0, 1, 2], vec![4]]));
+}
+
+#[test]
+fn test_topological_order_of_acyclic_subset() {
+    let graph = graph("(a: b)\n(b: a)\n(c: d b)\n(d: b)");
+    assert_eq!(graph.topological_order_of(&[2, 3, 1]), Ok(vec![2, 3, 1]));
+    assert_eq!(graph.topological_order_of(&[0, 1]), Err(vec![vec![0, 1]]));
+}
+
+#[test]
+fn test_topological_order_prefers_lower_indices() {
+    let graph = graph("(z: y)\n(x: y)\n(y: w)\n(w: end)");
+    assert_eq!(graph.topological_order(), Ok(vec![0, 1, 2, 3]));
+}
+
+#[test]
+fn test_nested_links_are_edges() {
+    let graph = graph("(outer: (inner: outer))");
+    assert_eq!(graph.cycles(), vec![vec![0, 1]]);
+}
+
+#[test]
+fn test_reachability() {
+    let graph = graph("(a: b c)\n(b: d)\n(c: x)\n(d: a)\n(e: a)");
+    assert_eq!(graph.reachable(1), vec![3, 0, 1, 2]);
+    assert_eq!(graph.reachable_from("c"), Some(vec![]));
+    assert_eq!(graph.reachable_from("e"), Some(vec![0, 1, 2, 3]));
+    assert_eq!(graph.reachable_from("x"), None);
+    assert!(graph.is_reachable(4, 3));
+    assert!(!graph.is_reachable(0, 4));
+}
+
+#[test]
+fn test_long_chain_does_not_overflow() {
+    let document: String = (0..20_000).map(|index| format!("(n{}: n{})\n", index, index + 1)).collect();
+    let graph = graph(&document);
+    assert!(!graph.has_cycle());
+    assert_eq!(graph.strongly_connected_components().len(), 20_000);
+}
@@ -1,4 +1,4 @@
-use links_notation::{parse_lino, LiNo};
+use links_notation::{format_links, parse_lino, parse_lino_to_links, LiNo};
 
 #[test]
 fn test_is_ref() {
@@ -89,4 +89,27 @@ fn test_quoted_references() {
     
     let output = parsed.to_string();
     assert!(output.contains("quoted id") && output.contains("value with spaces"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_format_quotes_references() {
+    let link = LiNo::Link {
+        id: Some("quoted id".to_string()),
+        values: vec![
+            LiNo::Ref("value with spaces".to_string()),
+            LiNo::Ref("'tis".to_string()),
+            LiNo::Ref("it's".to_string()),
+        ],
+    };
+    assert_eq!(link.format(false), r#"('quoted id': 'value with spaces' "'tis" it's)"#);
+    assert_eq!(link.format(true), r#"'quoted id': 'value with spaces' "'tis" it's"#);
+}
+
+#[test]
+fn test_format_links_round_trip() {
+    let input = "(papa: loves mama)\n(son lovesMama)\n('has space' ref)";
+    let links = parse_lino_to_links(input).expect("Failed to parse input");
+    assert_eq!(format_links(&links, false), input);
+    assert_eq!(format_links(&links, true), "papa: loves mama\nson lovesMama\n'has space' ref");
+    assert_eq!(parse_lino_to_links(&format_links(&links, true)).unwrap(), links);
+}
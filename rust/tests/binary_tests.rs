@@ -0,0 +1,158 @@
+use links_notation::binary::{from_bytes, read_links, to_bytes, write_links, MAX_DEPTH, MAX_NODES, VERSION};
+use links_notation::parse_lino_to_links;
+use links_notation::store::LinkStore;
+
+const DOCUMENT: &str = "(papa: loves mama)\nalone\n(a (b c) ())\n(x: 'with space' (b c) (b c))\n(é: \"'quoted'\")";
+
+#[test]
+fn test_round_trip() {
+    let links = parse_lino_to_links(DOCUMENT).unwrap();
+    let bytes = to_bytes(&links).unwrap();
+    assert_eq!(from_bytes(&bytes).unwrap(), links);
+
+    let mut buffer = Vec::new();
+    write_links(&mut buffer, &links).unwrap();
+    assert_eq!(buffer, bytes);
+    assert_eq!(read_links(buffer.as_slice()).unwrap(), links);
+
+    assert_eq!(from_bytes(&to_bytes(&[]).unwrap()).unwrap(), vec![]);
+}
+
+#[test]
+fn test_layout() {
+    let links = parse_lino_to_links("(a: b b)\n(a: b b)").unwrap();
+    let bytes = to_bytes(&links).unwrap();
+    assert_eq!(&bytes[..4], b"LINO");
+    assert_eq!(u16::from_le_bytes([bytes[4], bytes[5]]), VERSION);
+    // header, two strings, one shared link record, two values, two roots, checksum
+    assert_eq!(bytes.len(), 24 + (4 + 1) * 2 + 12 + 4 * 2 + 4 * 2 + 4);
+    let body = bytes.len() - 4;
+    assert_eq!(bytes[body..], crc32(&bytes[..body]).to_le_bytes());
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
+
+#[test]
+fn test_corruption_is_detected() {
+    let bytes = to_bytes(&parse_lino_to_links(DOCUMENT).unwrap()).unwrap();
+
+    let mut flipped = bytes.clone();
+    flipped[30] ^= 1;
+    assert_eq!(from_bytes(&flipped).unwrap_err().message(), "checksum mismatch");
+
+    assert!(from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(from_bytes(b"LINO").is_err());
+    assert_eq!(from_bytes(b"not links at all, really").unwrap_err().message(), "not a binary links file");
+}
+
+#[test]
+fn test_unsupported_version() {
+    let mut bytes = to_bytes(&parse_lino_to_links("a").unwrap()).unwrap();
+    bytes[4] = 2;
+    seal(&mut bytes);
+    assert_eq!(from_bytes(&bytes).unwrap_err().message(), "unsupported version 2");
+}
+
+#[test]
+fn test_nonzero_flags_are_rejected() {
+    let mut bytes = to_bytes(&parse_lino_to_links("a").unwrap()).unwrap();
+    bytes[6] = 1;
+    seal(&mut bytes);
+    assert_eq!(from_bytes(&bytes).unwrap_err().message(), "unsupported flags 0x1");
+}
+
+#[test]
+fn test_expansion_is_limited() {
+    // Each record points twice at the previous one, doubling the tree
+    let depth = 40u32;
+    let mut bytes = b"LINO".to_vec();
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    for count in [1, depth, depth * 2, 1] {
+        bytes.extend_from_slice(&count.to_le_bytes());
+    }
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.push(b'x');
+    for index in 0..depth {
+        for field in [0, index * 2, 2] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    for index in 0..depth {
+        let value = if index == 0 { 1 << 31 } else { index - 1 };
+        bytes.extend_from_slice(&value.to_le_bytes());
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(depth - 1).to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    seal(&mut bytes);
+
+    let error = from_bytes(&bytes).unwrap_err();
+    assert_eq!(error.message(), format!("links expand to more than {} nodes", MAX_NODES));
+    // The store keeps shared records shared
+    let store = LinkStore::from_bytes(&bytes).unwrap();
+    assert_eq!(store.len(), depth as usize + 1);
+}
+
+#[test]
+fn test_store_round_trip() {
+    let mut store = LinkStore::new();
+    store.ingest_document("(papa: loves mama)\n(mama: loves papa)\n(son: papa mama)").unwrap();
+    let loaded = LinkStore::from_bytes(&store.to_bytes().unwrap()).unwrap();
+    assert_eq!(loaded.len(), store.len());
+    let mut copy = LinkStore::new();
+    copy.ingest(&store.to_lino()).unwrap();
+    assert_eq!(loaded.to_text(), copy.to_text());
+    assert_eq!(loaded.query_names(&[Some("loves"), None]).len(), 2);
+}
+
+#[test]
+fn test_depth_is_limited() {
+    let links = from_bytes(&chain(MAX_DEPTH as u32)).unwrap();
+    assert_eq!(links.len(), 1);
+    // Far deeper than the stack allows
+    let error = from_bytes(&chain(200_000)).unwrap_err();
+    assert_eq!(error.message(), format!("links nest more than {} deep", MAX_DEPTH));
+}
+
+// A file whose records each hold the previous one
+fn chain(depth: u32) -> Vec<u8> {
+    let mut bytes = b"LINO".to_vec();
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    for count in [1, depth, depth, 1] {
+        bytes.extend_from_slice(&count.to_le_bytes());
+    }
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.push(b'x');
+    for index in 0..depth {
+        for field in [0, index, 1] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    for index in 0..depth {
+        let value = if index == 0 { 1 << 31 } else { index - 1 };
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(&(depth - 1).to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    seal(&mut bytes);
+    bytes
+}
+
+// Rewrites the checksum after changing the bytes before it
+fn seal(bytes: &mut [u8]) {
+    let body = bytes.len() - 4;
+    let checksum = crc32(&bytes[..body]);
+    bytes[body..].copy_from_slice(&checksum.to_le_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
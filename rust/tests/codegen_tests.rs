@@ -0,0 +1,85 @@
+use links_notation::codegen::generate;
+use links_notation::schema::Schema;
+use links_notation::{parse_lino_to_links, FromLino, LiNo, ToLino};
+
+mod family {
+    include!("data/family_schema.rs");
+}
+
+use family::{Address, Child, Color, Parent, Person, Tags};
+
+const SCHEMA: &str = include_str!("data/family.schema.lino");
+
+fn parsed(document: &str) -> LiNo<String> {
+    parse_lino_to_links(document).unwrap().remove(0)
+}
+
+#[test]
+fn test_generated_code_is_up_to_date() {
+    let code = generate(&Schema::parse(SCHEMA).unwrap()).unwrap();
+    assert_eq!(code, include_str!("data/family_schema.rs"));
+}
+
+#[test]
+fn test_generated_types_round_trip() {
+    let link = parsed("person: (name: alice) (age: 30) (color: light-blue) (address: (city: Paris) (zip: 75001))");
+    let person = Person::from_lino(&link).unwrap();
+    assert_eq!(
+        person,
+        Person {
+            name: "alice".to_string(),
+            age: Some(30.0),
+            color: Some(Color::LightBlue),
+            address: Some(Address {
+                city: "Paris".to_string(),
+                zip: Some(parsed("(zip: 75001)")),
+            }),
+            pet: None,
+        }
+    );
+    assert_eq!(person.to_lino(), link);
+
+    let tags = Tags::from_lino(&parsed("tags: a b")).unwrap();
+    assert_eq!(tags.values, vec!["a", "b"]);
+    assert_eq!(tags.to_lino(), parsed("tags: a b"));
+}
+
+#[test]
+fn test_recursive_and_prelude_named_types() {
+    let link = parsed("parent: (name: ann) (child: (name: bob) (parent: (name: cy)))");
+    let parent = Parent::from_lino(&link).unwrap();
+    let child: &Child = parent.child.as_deref().unwrap();
+    assert_eq!(child.name, "bob");
+    assert_eq!(child.parent.as_ref().unwrap().name.as_deref(), Some("cy"));
+    assert_eq!(parent.to_lino(), link);
+
+    let option = family::Option::from_lino(&parsed("option: (string: s) (vec: 1 2)")).unwrap();
+    assert_eq!(option.string.as_deref(), Some("s"));
+    assert_eq!(option.vec.unwrap().values, vec![1.0, 2.0]);
+}
+
+#[test]
+fn test_generated_types_errors() {
+    let error = Person::from_lino(&parsed("pet: cat")).unwrap_err();
+    assert_eq!(error.message(), "expected a `person` link, found `(pet: cat)`");
+    let error = Person::from_lino(&parsed("person: (name: bob) (color: blue)")).unwrap_err();
+    assert_eq!(error.message(), "field `color`: expected a `color`, found `blue`");
+}
+
+#[test]
+fn test_invalid_names() {
+    let error = generate(&Schema::parse("'1st': (values: ref)").unwrap()).unwrap_err();
+    assert_eq!(error.message(), "`1st` cannot be used as a Rust name");
+
+    let error = generate(&Schema::parse("person: (values: first-name first_name)").unwrap()).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "`first-name` and `first_name` in `person` are both written as the field `first_name`"
+    );
+
+    let error = generate(&Schema::parse("a-b: (values: ref)\na_b: (values: ref)").unwrap()).unwrap_err();
+    assert_eq!(error.message(), "`a-b` and `a_b` are both written as the type `AB`");
+
+    let error = generate(&Schema::parse("color: (arity: 1) (references: red Red)").unwrap()).unwrap_err();
+    assert_eq!(error.message(), "`red` and `Red` in `color` are both written as the variant `Red`");
+}
@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use links_notation::{parse_lino_to_links, FromLino, LiNo, ToLino};
+
+fn parsed(document: &str) -> LiNo<String> {
+    parse_lino_to_links(document).unwrap().remove(0)
+}
+
+#[test]
+fn test_primitives_round_trip() {
+    assert_eq!(42u8.to_lino(), LiNo::Ref("42".to_string()));
+    assert_eq!(i64::from_lino(&parsed("-7")).unwrap(), -7);
+    assert_eq!(f64::from_lino(&parsed("1.5")).unwrap(), 1.5);
+    assert!(bool::from_lino(&parsed("true")).unwrap());
+    assert_eq!(char::from_lino(&parsed("c")).unwrap(), 'c');
+    assert_eq!("".to_lino(), parsed("()"));
+    assert_eq!(String::from_lino(&parsed("()")).unwrap(), "");
+}
+
+#[test]
+fn test_containers() {
+    assert_eq!(vec![1, 2].to_lino(), parsed("(1 2)"));
+    assert_eq!(Vec::<u32>::from_lino(&parsed("(1 2)")).unwrap(), vec![1, 2]);
+    assert_eq!(Vec::<u32>::from_lino(&parsed("3")).unwrap(), vec![3]);
+    assert_eq!(None::<u32>.to_lino(), parsed("()"));
+    assert_eq!(Option::<u32>::from_lino(&parsed("5")).unwrap(), Some(5));
+    let map = BTreeMap::from([("a".to_string(), 1), ("b".to_string(), 2)]);
+    assert_eq!(map.to_lino(), parsed("(a: 1) (b: 2)"));
+    assert_eq!(BTreeMap::<String, i32>::from_lino(&map.to_lino()).unwrap(), map);
+}
+
+#[test]
+fn test_errors() {
+    let error = u8::from_lino(&parsed("300")).unwrap_err();
+    assert_eq!(error.message(), "expected an integer, found `300`");
+    let error = bool::from_lino(&parsed("(a b)")).unwrap_err();
+    assert_eq!(error.message(), "expected a boolean, found a link");
+    let error = Vec::<u8>::from_lino(&parsed("x: 1")).unwrap_err();
+    assert_eq!(error.message(), "expected a sequence, found the link `x`");
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "csv")]
+
+use links_notation::csv::{csv_to_lino, csv_to_text, lino_to_csv, text_to_csv, CsvOptions};
+use links_notation::LiNo;
+
+fn reference(value: &str) -> LiNo<String> {
+    LiNo::Ref(value.to_string())
+}
+
+#[test]
+fn test_rows_become_links() {
+    let links = csv_to_lino("papa,loves,mama\nson,likes,'ice cream'\n", &CsvOptions::csv()).unwrap();
+    assert_eq!(
+        links,
+        vec![
+            LiNo::Link { id: None, values: vec![reference("papa"), reference("loves"), reference("mama")] },
+            LiNo::Link { id: None, values: vec![reference("son"), reference("likes"), reference("ice cream")] },
+        ]
+    );
+}
+
+#[test]
+fn test_id_column_and_header() {
+    let options = CsvOptions::csv().with_header(true).with_id_column(0);
+    let text = csv_to_text("id,source,target\nloves,papa,mama\nlikes,son,'ice cream'\n", &options).unwrap();
+    assert_eq!(text, "(loves: papa mama)\n(likes: son 'ice cream')");
+}
+
+#[test]
+fn test_cells_hold_links_and_empty_values() {
+    let links = csv_to_lino("a,(b c),\n", &CsvOptions::csv()).unwrap();
+    assert_eq!(
+        links[0],
+        LiNo::Link {
+            id: None,
+            values: vec![
+                reference("a"),
+                LiNo::Link { id: None, values: vec![reference("b"), reference("c")] },
+                LiNo::Link { id: None, values: vec![] },
+            ]
+        }
+    );
+}
+
+#[test]
+fn test_tsv() {
+    let options = CsvOptions::tsv().with_id_column(1);
+    assert_eq!(csv_to_text("papa\tloves\tmama\n", &options).unwrap(), "(loves: papa mama)");
+    assert_eq!(text_to_csv("(loves: papa mama)", &options).unwrap(), "papa\tloves\tmama\n");
+}
+
+#[test]
+fn test_export_with_generated_header() {
+    let options = CsvOptions::csv().with_header(true).with_id_column(0);
+    assert_eq!(
+        text_to_csv("(loves: papa mama)\n(likes: son 'ice cream')", &options).unwrap(),
+        "id,value1,value2\nloves,papa,mama\nlikes,son,'ice cream'\n"
+    );
+}
+
+#[test]
+fn test_export_with_header_names() {
+    let options = CsvOptions::csv().with_header_names(["source", "target"]);
+    assert_eq!(text_to_csv("papa mama", &options).unwrap(), "source,target\npapa,mama\n");
+    assert_eq!(text_to_csv("", &options).unwrap(), "source,target\n");
+    assert!(text_to_csv("a b c", &options).is_err());
+}
+
+#[test]
+fn test_quoting_on_both_sides() {
+    let links = vec![LiNo::Link {
+        id: None,
+        values: vec![reference("a,b"), reference("say \"hi\""), reference("it's here")],
+    }];
+    let csv = lino_to_csv(&links, &CsvOptions::csv()).unwrap();
+    assert_eq!(csv, "\"a,b\",\"'say \"\"hi\"\"'\",\"\"\"it's here\"\"\"\n");
+    assert_eq!(csv_to_lino(&csv, &CsvOptions::csv()).unwrap(), links);
+}
+
+#[test]
+fn test_non_uniform_links_are_errors() {
+    assert!(text_to_csv("a b\nc d e", &CsvOptions::csv()).is_err());
+    assert!(text_to_csv("(x: a b)", &CsvOptions::csv()).is_err());
+    assert!(text_to_csv("a b", &CsvOptions::csv().with_id_column(0)).is_err());
+}
+
+#[test]
+fn test_invalid_cells_are_errors() {
+    let error = csv_to_lino("a,b\nc,\"d\ne\"\n", &CsvOptions::csv()).unwrap_err();
+    assert_eq!(error.message(), "line 2, field 2: expected a single link, found 2");
+    assert!(csv_to_lino("(a b),c\n", &CsvOptions::csv().with_id_column(0)).is_err());
+}
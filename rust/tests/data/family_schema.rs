@@ -0,0 +1,256 @@
+// Generated from a Links Notation schema; do not edit.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Person {
+    pub name: Name,
+    pub age: ::core::option::Option<Age>,
+    pub color: ::core::option::Option<Color>,
+    pub address: ::core::option::Option<Address>,
+    pub pet: ::core::option::Option<::links_notation::LiNo<::std::string::String>>,
+}
+
+impl ::links_notation::ToLino for Person {
+    fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+        let mut values = ::std::vec::Vec::new();
+        let value = &self.name;
+        values.push(::links_notation::convert::field("name", value));
+        if let ::core::option::Option::Some(value) = &self.age {
+            values.push(::links_notation::convert::field("age", value));
+        }
+        if let ::core::option::Option::Some(value) = &self.color {
+            values.push(::links_notation::convert::field("color", value));
+        }
+        if let ::core::option::Option::Some(value) = &self.address {
+            values.push(::links_notation::ToLino::to_lino(value));
+        }
+        if let ::core::option::Option::Some(value) = &self.pet {
+            values.push(::links_notation::ToLino::to_lino(value));
+        }
+        ::links_notation::LiNo::Link {
+            id: ::core::option::Option::Some("person".to_string()),
+            values,
+        }
+    }
+}
+
+impl ::links_notation::FromLino for Person {
+    fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {
+        let values = ::links_notation::convert::expect_id(lino, "person")?;
+        ::core::result::Result::Ok(Person {
+            name: ::links_notation::convert::read_field(values, "name")?,
+            age: ::links_notation::convert::read_field(values, "age")?,
+            color: ::links_notation::convert::read_field(values, "color")?,
+            address: ::links_notation::convert::find(values, "address").map(::links_notation::FromLino::from_lino).transpose()?,
+            pet: ::links_notation::convert::find(values, "pet").map(::links_notation::FromLino::from_lino).transpose()?,
+        })
+    }
+}
+
+pub type Name = ::std::string::String;
+
+pub type Age = ::core::primitive::f64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Color {
+    Red,
+    Green,
+    LightBlue,
+}
+
+impl ::links_notation::ToLino for Color {
+    fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+        let name = match self {
+            Color::Red => "red",
+            Color::Green => "green",
+            Color::LightBlue => "light-blue",
+        };
+        ::links_notation::LiNo::Ref(name.to_string())
+    }
+}
+
+impl ::links_notation::FromLino for Color {
+    fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {
+        match lino {
+            ::links_notation::LiNo::Ref(name) if name == "red" => ::core::result::Result::Ok(Color::Red),
+            ::links_notation::LiNo::Ref(name) if name == "green" => ::core::result::Result::Ok(Color::Green),
+            ::links_notation::LiNo::Ref(name) if name == "light-blue" => ::core::result::Result::Ok(Color::LightBlue),
+            _ => ::core::result::Result::Err(::links_notation::Error::new(format!("expected a `{}`, found `{}`", "color", lino))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Address {
+    pub city: City,
+    pub zip: ::core::option::Option<::links_notation::LiNo<::std::string::String>>,
+}
+
+impl ::links_notation::ToLino for Address {
+    fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+        let mut values = ::std::vec::Vec::new();
+        let value = &self.city;
+        values.push(::links_notation::convert::field("city", value));
+        if let ::core::option::Option::Some(value) = &self.zip {
+            values.push(::links_notation::ToLino::to_lino(value));
+        }
+        ::links_notation::LiNo::Link {
+            id: ::core::option::Option::Some("address".to_string()),
+            values,
+        }
+    }
+}
+
+impl ::links_notation::FromLino for Address {
+    fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {
+        let values = ::links_notation::convert::expect_id(lino, "address")?;
+        ::core::result::Result::Ok(Address {
+            city: ::links_notation::convert::read_field(values, "city")?,
+            zip: ::links_notation::convert::find(values, "zip").map(::links_notation::FromLino::from_lino).transpose()?,
+        })
+    }
+}
+
+pub type City = ::std::string::String;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tags {
+    pub values: ::std::vec::Vec<::std::string::String>,
+}
+
+impl ::links_notation::ToLino for Tags {
+    fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+        ::links_notation::LiNo::Link {
+            id: ::core::option::Option::Some("tags".to_string()),
+            values: self.values.iter().map(::links_notation::ToLino::to_lino).collect(),
+        }
+    }
+}
+
+impl ::links_notation::FromLino for Tags {
+    fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {
+        let values = ::links_notation::convert::expect_id(lino, "tags")?;
+        ::core::result::Result::Ok(Tags {
+            values: values.iter().map(::links_notation::FromLino::from_lino).collect::<::core::result::Result<_, _>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parent {
+    pub name: ::core::option::Option<Name>,
+    pub child: ::core::option::Option<::std::boxed::Box<Child>>,
+}
+
+impl ::links_notation::ToLino for Parent {
+    fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+        let mut values = ::std::vec::Vec::new();
+        if let ::core::option::Option::Some(value) = &self.name {
+            values.push(::links_notation::convert::field("name", value));
+        }
+        if let ::core::option::Option::Some(value) = &self.child {
+            values.push(::links_notation::ToLino::to_lino(value));
+        }
+        ::links_notation::LiNo::Link {
+            id: ::core::option::Option::Some("parent".to_string()),
+            values,
+        }
+    }
+}
+
+impl ::links_notation::FromLino for Parent {
+    fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {
+        let values = ::links_notation::convert::expect_id(lino, "parent")?;
+        ::core::result::Result::Ok(Parent {
+            name: ::links_notation::convert::read_field(values, "name")?,
+            child: ::links_notation::convert::find(values, "child").map(::links_notation::FromLino::from_lino).transpose()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Child {
+    pub name: Name,
+    pub parent: ::core::option::Option<::std::boxed::Box<Parent>>,
+}
+
+impl ::links_notation::ToLino for Child {
+    fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+        let mut values = ::std::vec::Vec::new();
+        let value = &self.name;
+        values.push(::links_notation::convert::field("name", value));
+        if let ::core::option::Option::Some(value) = &self.parent {
+            values.push(::links_notation::ToLino::to_lino(value));
+        }
+        ::links_notation::LiNo::Link {
+            id: ::core::option::Option::Some("child".to_string()),
+            values,
+        }
+    }
+}
+
+impl ::links_notation::FromLino for Child {
+    fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {
+        let values = ::links_notation::convert::expect_id(lino, "child")?;
+        ::core::result::Result::Ok(Child {
+            name: ::links_notation::convert::read_field(values, "name")?,
+            parent: ::links_notation::convert::find(values, "parent").map(::links_notation::FromLino::from_lino).transpose()?,
+        })
+    }
+}
+
+pub type String = ::std::string::String;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Option {
+    pub string: ::core::option::Option<String>,
+    pub vec: ::core::option::Option<Vec>,
+}
+
+impl ::links_notation::ToLino for Option {
+    fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+        let mut values = ::std::vec::Vec::new();
+        if let ::core::option::Option::Some(value) = &self.string {
+            values.push(::links_notation::convert::field("string", value));
+        }
+        if let ::core::option::Option::Some(value) = &self.vec {
+            values.push(::links_notation::ToLino::to_lino(value));
+        }
+        ::links_notation::LiNo::Link {
+            id: ::core::option::Option::Some("option".to_string()),
+            values,
+        }
+    }
+}
+
+impl ::links_notation::FromLino for Option {
+    fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {
+        let values = ::links_notation::convert::expect_id(lino, "option")?;
+        ::core::result::Result::Ok(Option {
+            string: ::links_notation::convert::read_field(values, "string")?,
+            vec: ::links_notation::convert::find(values, "vec").map(::links_notation::FromLino::from_lino).transpose()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vec {
+    pub values: ::std::vec::Vec<::core::primitive::f64>,
+}
+
+impl ::links_notation::ToLino for Vec {
+    fn to_lino(&self) -> ::links_notation::LiNo<::std::string::String> {
+        ::links_notation::LiNo::Link {
+            id: ::core::option::Option::Some("vec".to_string()),
+            values: self.values.iter().map(::links_notation::ToLino::to_lino).collect(),
+        }
+    }
+}
+
+impl ::links_notation::FromLino for Vec {
+    fn from_lino(lino: &::links_notation::LiNo<::std::string::String>) -> ::core::result::Result<Self, ::links_notation::Error> {
+        let values = ::links_notation::convert::expect_id(lino, "vec")?;
+        ::core::result::Result::Ok(Vec {
+            values: values.iter().map(::links_notation::FromLino::from_lino).collect::<::core::result::Result<_, _>>()?,
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use links_notation::diagram::{to_dot, to_graphml, to_mermaid};
+use links_notation::parse_lino_to_links;
+
+fn links(document: &str) -> Vec<links_notation::LiNo<String>> {
+    parse_lino_to_links(document).unwrap()
+}
+
+#[test]
+fn test_dot_doublets_are_plain_edges() {
+    assert_eq!(
+        to_dot(&links("(loves: papa mama)\n(son mama)")),
+        "digraph links {\n  \"papa\";\n  \"mama\";\n  \"son\";\n  \"papa\" -> \"mama\" [label=\"loves\"];\n  \"son\" -> \"mama\";\n}\n"
+    );
+}
+
+#[test]
+fn test_dot_longer_links_are_records() {
+    assert_eq!(
+        to_dot(&links("(fact: papa loves 'mama bear')")),
+        concat!(
+            "digraph links {\n",
+            "  \"papa\";\n",
+            "  \"loves\";\n",
+            "  \"mama bear\";\n",
+            "  \"fact\" [shape=record, label=\"{fact|<v0> papa|<v1> loves|<v2> mama\\ bear}\"];\n",
+            "  \"fact\":v0 -> \"papa\";\n",
+            "  \"fact\":v1 -> \"loves\";\n",
+            "  \"fact\":v2 -> \"mama bear\";\n",
+            "}\n"
+        )
+    );
+}
+
+#[test]
+fn test_dot_record_fields_are_escaped_once() {
+    let dot = to_dot(&links("(fact: a 'b c|d' 'say \"hi\"')"));
+    assert!(dot.contains(r#"  "fact" [shape=record, label="{fact|<v0> a|<v1> b\ c\|d|<v2> say\ \"hi\"}"];"#));
+    assert!(dot.contains("  \"fact\":v1 -> \"b c|d\";\n"));
+}
+
+#[test]
+fn test_singlets_and_nested_links() {
+    let dot = to_dot(&links("(parent: child)\n(lonely)\n((a b c) d)"));
+    assert!(dot.contains("  \"parent\" -> \"child\";\n"));
+    assert!(dot.contains("  \"lonely\";\n"));
+    assert!(dot.contains("  \"link1\" [shape=record, label=\"{|<v0> a|<v1> b|<v2> c}\"];\n"));
+    assert!(dot.contains("  \"link1\" -> \"d\";\n"));
+}
+
+#[test]
+fn test_generated_names_skip_used_names() {
+    let dot = to_dot(&links("link1 link2\n(a b c)"));
+    assert!(dot.contains("\"link3\" [shape=record"));
+}
+
+#[test]
+fn test_graphml() {
+    let graphml = to_graphml(&links("(loves: papa mama)\n(fact: papa loves mama)\n(x: (a b c) d)"));
+    assert!(graphml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml"));
+    assert!(graphml.contains("    <node id=\"papa\"><data key=\"label\">papa</data></node>\n"));
+    assert!(graphml.contains("    <edge source=\"papa\" target=\"mama\"><data key=\"label\">loves</data></edge>\n"));
+    assert!(graphml.contains(
+        "    <hyperedge><data key=\"label\">fact</data><endpoint node=\"papa\"/><endpoint node=\"loves\"/><endpoint node=\"mama\"/></hyperedge>\n"
+    ));
+    assert!(graphml.contains("    <node id=\"link1\"><data key=\"label\">link1</data></node>\n"));
+    assert!(graphml.contains("    <edge source=\"link1\" target=\"d\"><data key=\"label\">x</data></edge>\n"));
+    assert!(!graphml.contains("<node id=\"fact\""));
+    assert!(graphml.ends_with("  </graph>\n</graphml>\n"));
+}
+
+#[test]
+fn test_mermaid() {
+    assert_eq!(
+        to_mermaid(&links("(loves: papa mama)\n(fact: papa 'says \"hi\"' mama)")),
+        concat!(
+            "flowchart LR\n",
+            "  n1[\"papa\"]\n",
+            "  n2[\"mama\"]\n",
+            "  n3[\"says #quot;hi#quot;\"]\n",
+            "  n4[\"fact | papa | says #quot;hi#quot; | mama\"]\n",
+            "  n4 -->|1| n1\n",
+            "  n4 -->|2| n3\n",
+            "  n4 -->|3| n2\n",
+            "  n1 -->|\"loves\"| n2\n"
+        )
+    );
+}
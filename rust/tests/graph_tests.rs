@@ -0,0 +1,67 @@
+use links_notation::graph::{LinkGraph, ResolvedValue, Use};
+use links_notation::parse_lino_to_links;
+
+#[test]
+fn test_references_resolve_to_definitions() {
+    let graph = LinkGraph::parse("(papa: loves mama)\n(mama: loves papa)").unwrap();
+    assert_eq!(graph.len(), 2);
+    assert_eq!(graph.definition_of("papa"), Some(0));
+    assert_eq!(graph.definition_of("mama"), Some(1));
+    assert_eq!(graph.definition_of("loves"), None);
+    assert_eq!(
+        graph.link(0).values,
+        vec![
+            ResolvedValue::Reference { name: "loves".to_string(), target: None },
+            ResolvedValue::Reference { name: "mama".to_string(), target: Some(1) },
+        ]
+    );
+}
+
+#[test]
+fn test_forward_references_and_cycles() {
+    let graph = LinkGraph::parse("(a: b)\n(b: c)\n(c: a)\n(self: self)").unwrap();
+    assert_eq!(graph.outgoing(0), vec![1]);
+    assert_eq!(graph.outgoing(2), vec![0]);
+    assert_eq!(graph.outgoing(3), vec![3]);
+    assert!(graph.undefined().is_empty());
+}
+
+#[test]
+fn test_nested_links_have_indices() {
+    let graph = LinkGraph::parse("(outer: (inner: x) y)\n(z inner)").unwrap();
+    assert_eq!(graph.len(), 3);
+    assert_eq!(graph.roots(), &[0, 2]);
+    assert_eq!(graph.link(1).id.as_deref(), Some("inner"));
+    assert_eq!(graph.link(1).parent, Some(0));
+    assert_eq!(graph.link(0).values[0], ResolvedValue::Link(1));
+    assert_eq!(graph.outgoing(2), vec![1]);
+}
+
+#[test]
+fn test_uses_and_neighbours() {
+    let graph = LinkGraph::parse("(a: b c)\n(b: a)\n(c: x)\n(d: a a)").unwrap();
+    assert_eq!(
+        graph.uses_of("a"),
+        &[Use { link: 1, position: 0 }, Use { link: 3, position: 0 }, Use { link: 3, position: 1 }]
+    );
+    assert!(graph.uses_of("missing").is_empty());
+    assert_eq!(graph.outgoing(0), vec![1, 2]);
+    assert_eq!(graph.incoming(0), vec![1, 3]);
+    assert_eq!(graph.neighbours(0), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_undefined_and_duplicate_ids() {
+    let graph = LinkGraph::parse("(a: x b)\n(a: y)\n(b: x)").unwrap();
+    assert_eq!(graph.undefined(), vec!["x", "y"]);
+    assert_eq!(graph.duplicates(), vec![("a", &[0, 1][..])]);
+    assert_eq!(graph.definition_of("a"), Some(0));
+}
+
+#[test]
+fn test_top_level_references() {
+    let links = parse_lino_to_links("(a)\n(a: b)").unwrap();
+    let graph = LinkGraph::new(&links);
+    assert_eq!(graph.link(0).id, None);
+    assert_eq!(graph.outgoing(0), vec![1]);
+}
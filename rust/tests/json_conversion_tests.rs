@@ -0,0 +1,94 @@
+#![cfg(feature = "json")]
+
+use links_notation::json::{json_to_lino, json_to_text, lino_to_json, text_to_json};
+use links_notation::{parse_lino_to_links, LiNo};
+use serde_json::json;
+
+fn assert_round_trip(value: serde_json::Value) {
+    let lino = json_to_lino(&value);
+    assert_eq!(lino_to_json(&lino).unwrap(), value, "LiNo round trip of {}", value);
+
+    let text = json_to_text(&value).unwrap();
+    assert_eq!(text_to_json(&text).unwrap(), value, "text round trip of {} via {:?}", value, text);
+}
+
+#[test]
+fn test_scalars_become_references() {
+    assert_eq!(json_to_lino(&json!(null)), LiNo::Ref("null".to_string()));
+    assert_eq!(json_to_lino(&json!(true)), LiNo::Ref("true".to_string()));
+    assert_eq!(json_to_lino(&json!(-1.5)), LiNo::Ref("-1.5".to_string()));
+    assert_eq!(json_to_lino(&json!("text")), LiNo::Ref("text".to_string()));
+}
+
+#[test]
+fn test_ambiguous_strings_are_tagged() {
+    assert_eq!(json_to_text(&json!("42")).unwrap(), "string: 42");
+    assert_eq!(json_to_text(&json!("null")).unwrap(), "string: null");
+    assert_eq!(json_to_text(&json!("")).unwrap(), "string: ()");
+}
+
+#[test]
+fn test_object_to_text() {
+    let value = json!({"name": "my service", "port": 8080, "tags": ["web", "api"]});
+    assert_eq!(
+        json_to_text(&value).unwrap(),
+        "name: 'my service'\nport: 8080\ntags: (web api)"
+    );
+}
+
+#[test]
+fn test_arrays_to_text() {
+    assert_eq!(json_to_text(&json!([1, 2, 3])).unwrap(), "1\n2\n3");
+    assert_eq!(json_to_text(&json!({"a": [1]})).unwrap(), "((a: (array: 1)))");
+    assert_eq!(json_to_text(&json!({"a": []})).unwrap(), "((a: (array: ())))");
+}
+
+#[test]
+fn test_text_to_json() {
+    let value = text_to_json("name: svc\nreplicas: 3\nenabled: true\nlabels: ((app: web) (tier: front))").unwrap();
+    assert_eq!(
+        value,
+        json!({"name": "svc", "replicas": 3, "enabled": true, "labels": {"app": "web", "tier": "front"}})
+    );
+}
+
+#[test]
+fn test_parsed_links_to_json() {
+    let links = parse_lino_to_links("(1 2 3)").unwrap();
+    assert_eq!(lino_to_json(&links[0]).unwrap(), json!([1, 2, 3]));
+}
+
+#[test]
+fn test_unexpected_link_id_is_an_error() {
+    let lino = LiNo::Link { id: Some("other".to_string()), values: vec![LiNo::Ref("x".to_string())] };
+    assert!(lino_to_json(&lino).is_err());
+}
+
+#[test]
+fn test_unrepresentable_string_is_an_error() {
+    assert!(json_to_text(&json!("it's \"both\"")).is_err());
+    assert!(json_to_text(&json!({"": 1})).is_err());
+}
+
+#[test]
+fn test_round_trips() {
+    assert_round_trip(json!(null));
+    assert_round_trip(json!(false));
+    assert_round_trip(json!(12.5));
+    assert_round_trip(json!("plain"));
+    assert_round_trip(json!("with space: and (parens)"));
+    assert_round_trip(json!("it's"));
+    assert_round_trip(json!("multi\nline"));
+    assert_round_trip(json!(["1", "true", ""]));
+    assert_round_trip(json!({}));
+    assert_round_trip(json!([]));
+    assert_round_trip(json!([[]]));
+    assert_round_trip(json!([{}]));
+    assert_round_trip(json!([[1]]));
+    assert_round_trip(json!([[1, 2]]));
+    assert_round_trip(json!([{"a": 1}, {"b": 2}]));
+    assert_round_trip(json!([{"a": 1}]));
+    assert_round_trip(json!({"string": "x", "array": [1, 2]}));
+    assert_round_trip(json!({"only": {"nested": {"deep": [null, {"x": "y"}]}}}));
+    assert_round_trip(json!([["a", "b"], ["c", "d"]]));
+}
@@ -148,4 +148,71 @@ fn link_simplify_test() {
         assert_eq!(values.len(), 1);
         assert_eq!(values[0], single_ref);
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn link_accessors_test() {
+    let link = links_notation::parse_lino_to_links("(papa: loves (mama: cooks))").unwrap().remove(0);
+    assert_eq!(link.id(), Some(&"papa".to_string()));
+    assert_eq!(link.len(), 2);
+    assert!(!link.is_empty());
+    assert_eq!(link[0], LiNo::from("loves"));
+    assert_eq!(link.get(1).and_then(LiNo::id).map(String::as_str), Some("mama"));
+    assert_eq!(link.get(2), None);
+    assert_eq!(link[1][0], "cooks".into());
+
+    let reference = LiNo::from("loves");
+    assert_eq!(reference.id(), None);
+    assert!(reference.values().is_empty());
+    assert_eq!(reference.len(), 0);
+}
+
+#[test]
+fn link_values_mut_test() {
+    let mut link = LiNo::from(vec![LiNo::from("a"), LiNo::from("b")]);
+    link[0] = "c".into();
+    for value in link.values_mut() {
+        if let LiNo::Ref(name) = value {
+            name.push('!');
+        }
+    }
+    *link.get_mut(1).unwrap() = LiNo::from(vec![]);
+    assert_eq!(link.to_string(), "(c! ())");
+    assert!(LiNo::from("ref").values_mut().is_empty());
+}
+
+#[test]
+fn link_find_by_id_test() {
+    let document = links_notation::parse_lino("(papa: loves mama)\n(son: (dog: barks))").unwrap();
+    assert_eq!(document.find_by_id("dog").map(|link| link.to_string()), Some("(dog: barks)".to_string()));
+    assert_eq!(document.find_by_id("papa").unwrap().len(), 2);
+    assert_eq!(document.find_by_id("barks"), None);
+}
+
+#[test]
+fn link_map_test() {
+    let link = links_notation::parse_lino_to_links("(3: 1 (2 4))").unwrap().remove(0);
+    let numbers: LiNo<u32> = link.as_ref().map(|value| value.parse().unwrap());
+    assert_eq!(numbers.id(), Some(&3));
+    assert_eq!(numbers[1][1], LiNo::Ref(4));
+    assert_eq!(numbers.clone().map(|value| value * 2).to_string(), "(6: 2 (4 8))");
+
+    let mut seen = vec![];
+    link.clone().map(|value| seen.push(value));
+    assert_eq!(seen, vec!["3", "1", "2", "4"]);
+
+    assert_eq!(link.clone().try_map(|value| value.parse::<u32>()), Ok(numbers));
+    let failed = LiNo::from(vec!["1".into(), "x".into()]).try_map(|value: String| value.parse::<u32>());
+    assert!(failed.is_err());
+}
+
+#[test]
+fn link_into_parts_test() {
+    let link = LiNo::Link {
+        id: Some("id".to_string()),
+        values: vec!["a".into()],
+    };
+    assert_eq!(link.into_parts(), (Some("id".to_string()), vec![LiNo::from("a")]));
+    assert_eq!(LiNo::from("a").into_parts(), (Some("a".to_string()), vec![]));
+    assert_eq!(LiNo::<String>::from(vec![]).into_parts(), (None, vec![]));
+}
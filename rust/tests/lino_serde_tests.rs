@@ -0,0 +1,53 @@
+#![cfg(feature = "serde")]
+
+use links_notation::{parse_lino_to_links, LiNo};
+use serde_json::json;
+
+#[test]
+fn test_ref_serializes_as_plain_string() {
+    let reference = LiNo::Ref("papa".to_string());
+    assert_eq!(serde_json::to_value(&reference).unwrap(), json!("papa"));
+}
+
+#[test]
+fn test_link_serializes_with_id_and_values() {
+    let links = parse_lino_to_links("(papa: loves mama)\n(son lovesMama)").unwrap();
+    assert_eq!(
+        serde_json::to_value(&links).unwrap(),
+        json!([
+            {"id": "papa", "values": ["loves", "mama"]},
+            {"id": null, "values": ["son", "lovesMama"]}
+        ])
+    );
+}
+
+#[test]
+fn test_nested_link_json_round_trip() {
+    let links = parse_lino_to_links("(outer: (inner: a b) c)\n(x (y z))").unwrap();
+    let json = serde_json::to_string(&links).unwrap();
+    let restored: Vec<LiNo<String>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, links);
+}
+
+#[test]
+fn test_deserialize_link_with_missing_fields() {
+    let link: LiNo<String> = serde_json::from_value(json!({"values": ["a"]})).unwrap();
+    assert_eq!(link, LiNo::Link { id: None, values: vec![LiNo::Ref("a".to_string())] });
+
+    let link: LiNo<String> = serde_json::from_value(json!({"id": "only"})).unwrap();
+    assert_eq!(link, LiNo::Link { id: Some("only".to_string()), values: vec![] });
+}
+
+#[test]
+fn test_deserialize_rejects_unknown_fields() {
+    let result = serde_json::from_value::<LiNo<String>>(json!({"id": "a", "extra": 1}));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_non_string_references() {
+    let link = LiNo::Link { id: Some(1u32), values: vec![LiNo::Ref(2), LiNo::Ref(3)] };
+    let json = serde_json::to_value(&link).unwrap();
+    assert_eq!(json, json!({"id": 1, "values": [2, 3]}));
+    assert_eq!(serde_json::from_value::<LiNo<u32>>(json).unwrap(), link);
+}
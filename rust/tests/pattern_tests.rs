@@ -0,0 +1,105 @@
+use links_notation::parse_lino_to_links;
+use links_notation::pattern::{Binding, Bindings, Pattern};
+use links_notation::LiNo;
+
+fn reference(name: &str) -> LiNo<String> {
+    LiNo::Ref(name.to_string())
+}
+
+fn value(bindings: &Bindings, name: &str) -> String {
+    bindings[name].as_value().unwrap().format(true)
+}
+
+#[test]
+fn test_bindings() {
+    let links = parse_lino_to_links("(f1: alice knows bob)\n(f2: bob knows carol)\n(f3: alice likes tea)").unwrap();
+    let pattern = Pattern::parse("($x: $y knows $z)").unwrap();
+    assert_eq!(pattern.variables(), vec!["x", "y", "z"]);
+
+    let matches = pattern.find_all(&links);
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].link, &links[0]);
+    assert_eq!(value(&matches[0].bindings, "x"), "f1");
+    assert_eq!(value(&matches[0].bindings, "y"), "alice");
+    assert_eq!(value(&matches[1].bindings, "z"), "carol");
+}
+
+#[test]
+fn test_patterns_without_id_match_any_id() {
+    let links = parse_lino_to_links("(f1: alice knows bob)\n(carol knows dave)").unwrap();
+    assert_eq!(Pattern::parse("($y knows $z)").unwrap().find_all(&links).len(), 2);
+    assert_eq!(Pattern::parse("($_: $y knows $z)").unwrap().find_all(&links).len(), 1);
+    assert_eq!(Pattern::parse("(f1: $_ knows $_)").unwrap().find_all(&links).len(), 1);
+}
+
+#[test]
+fn test_repeated_variables_unify() {
+    let links = parse_lino_to_links("(narcissus loves narcissus)\n(romeo loves juliet)\n(self: self loops)").unwrap();
+    let matches = Pattern::parse("($x loves $x)").unwrap().find_all(&links);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(value(&matches[0].bindings, "x"), "narcissus");
+
+    let matches = Pattern::parse("($x: $x $_)").unwrap().find_all(&links);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].link, &links[2]);
+}
+
+#[test]
+fn test_nested_patterns() {
+    let links = parse_lino_to_links("(son: (likes ball) (likes (red car)))\n(dog: (likes bone))").unwrap();
+    let matches = Pattern::parse("(likes ($colour car))").unwrap().find_all(&links);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(value(&matches[0].bindings, "colour"), "red");
+
+    let matches = Pattern::parse("($who: (likes $what) ..)").unwrap().find_all(&links);
+    assert!(matches.is_empty());
+    let matches = Pattern::parse("($who: (likes $what) $rest..)").unwrap().find_all(&links);
+    assert_eq!(matches.len(), 2);
+    assert_eq!(value(&matches[1].bindings, "what"), "bone");
+    assert_eq!(matches[1].bindings["rest"], Binding::Values(vec![]));
+
+    // Variables can bind whole links
+    let matches = Pattern::parse("(son: $first $second)").unwrap().find_all(&links);
+    assert_eq!(value(&matches[0].bindings, "second"), "likes (red car)");
+}
+
+#[test]
+fn test_sequence_variables() {
+    let pattern = Pattern::parse("(list: $head $tail..)").unwrap();
+    let bindings = pattern.matches(&parse_lino_to_links("(list: a b c)").unwrap()[0]).unwrap();
+    assert_eq!(bindings["head"], Binding::Value(reference("a")));
+    assert_eq!(bindings["tail"].as_values().unwrap(), &[reference("b"), reference("c")]);
+
+    let pattern = Pattern::parse("($before.. x $after..)").unwrap();
+    let bindings = pattern.matches(&parse_lino_to_links("(a x b x c)").unwrap()[0]).unwrap();
+    assert_eq!(bindings["before"], Binding::Values(vec![reference("a")]));
+    assert_eq!(bindings["after"].as_values().unwrap().len(), 3);
+
+    let pattern = Pattern::parse("($xs.. $xs..)").unwrap();
+    assert!(pattern.matches(&parse_lino_to_links("(a b a b)").unwrap()[0]).is_some());
+    assert!(pattern.matches(&parse_lino_to_links("(a b a)").unwrap()[0]).is_none());
+}
+
+#[test]
+fn test_literals_match_exactly() {
+    let pattern = Pattern::parse("(papa loves mama)").unwrap();
+    assert!(pattern.variables().is_empty());
+    let links = parse_lino_to_links("(papa loves mama)\n(papa loves (mama))\n(papa loves mama too)").unwrap();
+    assert_eq!(pattern.find_all(&links).len(), 2);
+    assert!(Pattern::parse("a\nb").is_err());
+}
+
+#[test]
+fn test_nested_matches_backtrack() {
+    let pattern = Pattern::parse("(($xs.. $ys..) $xs..)").unwrap();
+    let bindings = pattern.matches(&parse_lino_to_links("((a b) a)").unwrap()[0]).unwrap();
+    assert_eq!(bindings["xs"], Binding::Values(vec![reference("a")]));
+    assert_eq!(bindings["ys"], Binding::Values(vec![reference("b")]));
+    assert!(pattern.matches(&parse_lino_to_links("((a b) c)").unwrap()[0]).is_none());
+}
+
+#[test]
+fn test_unnamed_sequence_variables_are_rejected() {
+    assert!(Pattern::parse("(a $..)").is_err());
+    assert!(Pattern::parse("($..: a)").is_err());
+}
@@ -0,0 +1,97 @@
+use links_notation::query::{query, query_links, Query};
+use links_notation::{parse_lino, parse_lino_to_links, LiNo};
+
+const DOCUMENT: &str = "(papa: loves mama)
+(mama: loves papa)
+(son: (likes ball) (likes 'red car'))
+(daughter lovesMama)
+(lovesMama: loves mama)";
+
+fn texts(links: Vec<&LiNo<String>>) -> Vec<String> {
+    links.into_iter().map(|link| link.format(true)).collect()
+}
+
+#[test]
+fn test_select_by_id() {
+    let document = parse_lino(DOCUMENT).unwrap();
+    assert_eq!(texts(query(&document, "/papa").unwrap()), vec!["papa: loves mama"]);
+    assert_eq!(texts(query(&document, "papa").unwrap()), vec!["papa: loves mama"]);
+    assert_eq!(texts(query(&document, "/papa/*").unwrap()), vec!["loves", "mama"]);
+    assert_eq!(query(&document, "/nobody").unwrap(), Vec::<&LiNo<String>>::new());
+}
+
+#[test]
+fn test_descendants() {
+    let document = parse_lino(DOCUMENT).unwrap();
+    assert_eq!(
+        texts(query(&document, "//mama").unwrap()),
+        vec!["mama", "mama: loves papa", "mama"]
+    );
+    assert_eq!(texts(query(&document, "/son//'red car'").unwrap()), vec!["'red car'"]);
+    assert_eq!(query(&document, "//*").unwrap().len(), 19);
+}
+
+#[test]
+fn test_value_patterns() {
+    let document = parse_lino(DOCUMENT).unwrap();
+    assert_eq!(
+        texts(query(&document, "/(loves *)").unwrap()),
+        vec!["papa: loves mama", "mama: loves papa", "lovesMama: loves mama"]
+    );
+    assert_eq!(
+        texts(query(&document, "/(*: * mama)").unwrap()),
+        vec!["papa: loves mama", "lovesMama: loves mama"]
+    );
+    assert_eq!(texts(query(&document, "/(daughter ..)").unwrap()), vec!["daughter lovesMama"]);
+    assert_eq!(texts(query(&document, "/(son: .. (likes ball) ..)").unwrap()), vec!["son: (likes ball) (likes 'red car')"]);
+    assert_eq!(texts(query(&document, "//(likes *)[depth=2]").unwrap()), vec!["likes ball", "likes 'red car'"]);
+    assert!(query(&document, "/(loves)").unwrap().is_empty());
+}
+
+#[test]
+fn test_positions_and_depth() {
+    let document = parse_lino(DOCUMENT).unwrap();
+    assert_eq!(texts(query(&document, "/*[2]").unwrap()), vec!["mama: loves papa"]);
+    assert_eq!(texts(query(&document, "/*[-1]").unwrap()), vec!["lovesMama: loves mama"]);
+    assert_eq!(texts(query(&document, "/son/*[-1]/*[2]").unwrap()), vec!["'red car'"]);
+    assert_eq!(texts(query(&document, "//*[depth>2]").unwrap()), vec!["likes", "ball", "likes", "'red car'"]);
+    assert_eq!(query(&document, "//*[depth < 2]").unwrap().len(), 5);
+}
+
+#[test]
+fn test_follow_references() {
+    let document = parse_lino(DOCUMENT).unwrap();
+    assert_eq!(texts(query(&document, "/papa/*[2]/->").unwrap()), vec!["mama: loves papa"]);
+    assert_eq!(texts(query(&document, "/papa/mama/->/papa/->").unwrap()), vec!["papa: loves mama"]);
+    assert_eq!(texts(query(&document, "/(daughter *)/*[2]/->/*").unwrap()), vec!["loves", "mama"]);
+    assert_eq!(
+        texts(query(&document, "//->").unwrap()),
+        vec!["papa: loves mama", "mama: loves papa", "lovesMama: loves mama"]
+    );
+}
+
+#[test]
+fn test_query_links() {
+    let links = parse_lino_to_links(DOCUMENT).unwrap();
+    let compiled = Query::parse("/(*: loves *)/*[1]").unwrap();
+    assert_eq!(compiled.select_in(&links).len(), 3);
+    assert_eq!(texts(query_links(&links, "//ball").unwrap()), vec!["ball"]);
+
+    let single = LiNo::Link {
+        id: Some("pair"),
+        values: vec![LiNo::Ref("a"), LiNo::Ref("b")],
+    };
+    assert_eq!(query(&single, "/b").unwrap(), vec![&LiNo::Ref("b")]);
+}
+
+#[test]
+fn test_invalid_queries() {
+    for invalid in ["", "/", "papa/", "(a b", "*[0]", "*[depth~2]", "*[2", "(a: b: c)", "a b", "'a"] {
+        assert!(Query::parse(invalid).is_err(), "{}", invalid);
+    }
+    let error = Query::parse("/papa/(a *").unwrap_err();
+    assert_eq!(error.column(), Some(7));
+    let error = Query::parse("*[depth=-2]").unwrap_err();
+    assert_eq!(error.message(), "depths cannot be negative");
+    assert_eq!(error.column(), Some(9));
+}
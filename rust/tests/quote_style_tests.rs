@@ -0,0 +1,86 @@
+use links_notation::parser::{format_document, Link};
+use links_notation::{parse_lino_to_ast, QuoteStyle};
+
+#[test]
+fn test_bare_reference_style() {
+    let links = parse_lino_to_ast("papa").unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].id, Some("papa".to_string()));
+    assert_eq!(links[0].quote_style, QuoteStyle::Bare);
+}
+
+#[test]
+fn test_quoted_reference_styles() {
+    let links = parse_lino_to_ast(r#"("quoted id": 'single value' "double value" bare)"#).unwrap();
+    let link = &links[0];
+    assert_eq!(link.id, Some("quoted id".to_string()));
+    assert_eq!(link.quote_style, QuoteStyle::DoubleQuoted);
+
+    let styles: Vec<QuoteStyle> = link.values.iter().map(|v| v.quote_style).collect();
+    assert_eq!(styles, vec![QuoteStyle::SingleQuoted, QuoteStyle::DoubleQuoted, QuoteStyle::Bare]);
+}
+
+#[test]
+fn test_multiline_reference_style() {
+    let links = parse_lino_to_ast("('line1\nline2' other)").unwrap();
+    let value = &links[0].values[0];
+    assert_eq!(value.id, Some("line1\nline2".to_string()));
+    assert_eq!(value.quote_style, QuoteStyle::SingleQuoted);
+}
+
+#[test]
+fn test_indented_id_quote_style() {
+    let links = parse_lino_to_ast("'my id':\n  value").unwrap();
+    assert!(links[0].is_indented_id);
+    assert_eq!(links[0].quote_style, QuoteStyle::SingleQuoted);
+}
+
+#[test]
+fn test_format_preserves_quote_style() {
+    let input = r#"("quoted id": 'single value' "double value" bare)"#;
+    let links = parse_lino_to_ast(input).unwrap();
+    assert_eq!(format_document(&links), input);
+}
+
+#[test]
+fn test_format_preserves_quote_style_in_indented_document() {
+    let input = "\"a b\":\n  'c d'\n  e\nplain\n  (x: \"y\")";
+    let links = parse_lino_to_ast(input).unwrap();
+    assert_eq!(format_document(&links), input);
+}
+
+#[test]
+fn test_format_keeps_bare_references_with_quotes() {
+    let input = "(it's a\"b)\n(x: don't)";
+    let links = parse_lino_to_ast(input).unwrap();
+    assert_eq!(links[0].values[0].quote_style, QuoteStyle::Bare);
+    assert_eq!(format_document(&links), input);
+    assert_eq!(parse_lino_to_ast(&format_document(&links)).unwrap(), links);
+}
+
+#[test]
+fn test_format_falls_back_when_style_cannot_represent_reference() {
+    let link = Link::new_singlet("has space".to_string());
+    assert_eq!(link.to_string(), "'has space'");
+
+    let link = Link::new_singlet("it's mine".to_string()).with_quote_style(QuoteStyle::SingleQuoted);
+    assert_eq!(link.to_string(), "\"it's mine\"");
+}
+
+#[test]
+fn test_minimal_quote_style() {
+    assert_eq!(QuoteStyle::minimal_for("simple"), QuoteStyle::Bare);
+    assert_eq!(QuoteStyle::minimal_for("has:colon"), QuoteStyle::SingleQuoted);
+    assert_eq!(QuoteStyle::minimal_for("say \"hi\""), QuoteStyle::SingleQuoted);
+    assert_eq!(QuoteStyle::minimal_for("it's"), QuoteStyle::Bare);
+    assert_eq!(QuoteStyle::minimal_for("'tis"), QuoteStyle::DoubleQuoted);
+    assert_eq!(QuoteStyle::minimal_for("\"hi\""), QuoteStyle::SingleQuoted);
+    assert!(QuoteStyle::DoubleQuoted.is_quoted());
+    assert!(!QuoteStyle::Bare.is_quoted());
+}
+
+#[test]
+fn test_minimal_quote_style_with_apostrophe_and_space() {
+    assert_eq!(QuoteStyle::minimal_for("it's mine"), QuoteStyle::DoubleQuoted);
+    assert!(QuoteStyle::minimal_for("it's mine").can_represent("it's mine"));
+}
@@ -0,0 +1,148 @@
+use links_notation::rdf::{
+    lino_to_ntriples, lino_to_turtle, ntriples_to_lino, ntriples_to_text, text_to_ntriples, turtle_to_lino,
+};
+use links_notation::LiNo;
+
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+
+fn triple(subject: &str, predicate: &str, object: &str) -> LiNo<String> {
+    LiNo::Link {
+        id: None,
+        values: vec![
+            LiNo::Ref(subject.to_string()),
+            LiNo::Ref(predicate.to_string()),
+            LiNo::Ref(object.to_string()),
+        ],
+    }
+}
+
+#[test]
+fn test_ntriples_terms_become_references() {
+    let document = r#"
+# people
+<http://example.org/alice> <http://xmlns.com/foaf/0.1/knows> _:bob .
+_:bob <http://xmlns.com/foaf/0.1/name> "Bob \"B\"\n"@en .
+<http://example.org/alice> <http://example.org/age> "42"^^<http://www.w3.org/2001/XMLSchema#integer> .
+<http://example.org/alice> <http://example.org/nick> "al"^^<http://www.w3.org/2001/XMLSchema#string> .
+"#;
+    assert_eq!(
+        ntriples_to_lino(document).unwrap(),
+        vec![
+            triple("http://example.org/alice", "http://xmlns.com/foaf/0.1/knows", "_:bob"),
+            triple("_:bob", "http://xmlns.com/foaf/0.1/name", "\"Bob \\\"B\\\"\\n\"@en"),
+            triple(
+                "http://example.org/alice",
+                "http://example.org/age",
+                &format!("\"42\"^^<{}>", XSD_INTEGER)
+            ),
+            triple("http://example.org/alice", "http://example.org/nick", "\"al\""),
+        ]
+    );
+}
+
+#[test]
+fn test_ntriples_to_text() {
+    assert_eq!(
+        ntriples_to_text("<http://a> <http://b> \"c d\" .").unwrap(),
+        "('http://a' 'http://b' '\"c d\"')"
+    );
+}
+
+#[test]
+fn test_literals_with_apostrophes() {
+    let document = "<http://x/a> <http://x/name> \"O'Brien\" .\n<http://x/a> <http://x/said> \"\\\"it's\\\"\"@en .\n";
+    let links = ntriples_to_lino(document).unwrap();
+    assert_eq!(links[0], triple("http://x/a", "http://x/name", "\"O\\u0027Brien\""));
+    let text = ntriples_to_text(document).unwrap();
+    assert_eq!(text.lines().next().unwrap(), "('http://x/a' 'http://x/name' '\"O\\u0027Brien\"')");
+    assert_eq!(text_to_ntriples(&text).unwrap(), document);
+}
+
+#[test]
+fn test_named_triplets_to_ntriples() {
+    let document = "(fact1: 'http://example.org/papa' 'http://example.org/loves' 'http://example.org/mama')";
+    assert_eq!(
+        text_to_ntriples(document).unwrap(),
+        "<http://example.org/papa> <http://example.org/loves> <http://example.org/mama> .\n"
+    );
+}
+
+#[test]
+fn test_ntriples_round_trip() {
+    let document = "<http://a> <http://b> _:c .\n_:c <http://d> \"e\\\\f\"@en-GB .\n<http://\\u0020x> <http://b> \"1\"^^<http://t> .\n";
+    let links = ntriples_to_lino(document).unwrap();
+    assert_eq!(lino_to_ntriples(&links).unwrap(), document);
+    assert_eq!(ntriples_to_lino(&lino_to_ntriples(&links).unwrap()).unwrap(), links);
+}
+
+#[test]
+fn test_turtle() {
+    let document = r#"
+@prefix ex: <http://example.org/> .
+PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+
+ex:alice a foaf:Person ;
+    foaf:knows ex:bob, [ foaf:name 'Carol' ] ;
+    ex:age 42 ;
+    ex:active true .
+"#;
+    assert_eq!(
+        turtle_to_lino(document).unwrap(),
+        vec![
+            triple(
+                "http://example.org/alice",
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+                "http://xmlns.com/foaf/0.1/Person"
+            ),
+            triple("http://example.org/alice", "http://xmlns.com/foaf/0.1/knows", "http://example.org/bob"),
+            triple("_:anon1", "http://xmlns.com/foaf/0.1/name", "\"Carol\""),
+            triple("http://example.org/alice", "http://xmlns.com/foaf/0.1/knows", "_:anon1"),
+            triple("http://example.org/alice", "http://example.org/age", &format!("\"42\"^^<{}>", XSD_INTEGER)),
+            triple(
+                "http://example.org/alice",
+                "http://example.org/active",
+                "\"true\"^^<http://www.w3.org/2001/XMLSchema#boolean>"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_lino_to_turtle() {
+    let links = vec![
+        triple("http://example.org/alice", "http://www.w3.org/1999/02/22-rdf-syntax-ns#type", "http://example.org/Person"),
+        triple("http://example.org/alice", "http://example.org/age", &format!("\"42\"^^<{}>", XSD_INTEGER)),
+        triple("_:b", "http://example.org/name", "\"Bob\"@en"),
+    ];
+    let prefixes = [("ex", "http://example.org/"), ("xsd", "http://www.w3.org/2001/XMLSchema#")];
+    let turtle = lino_to_turtle(&links, &prefixes).unwrap();
+    assert_eq!(
+        turtle,
+        "@prefix ex: <http://example.org/> .\n@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\n\
+         ex:alice a ex:Person ;\n    ex:age \"42\"^^xsd:integer .\n_:b ex:name \"Bob\"@en .\n"
+    );
+    assert_eq!(turtle_to_lino(&turtle).unwrap(), links);
+}
+
+#[test]
+fn test_invalid_links_are_errors() {
+    assert!(text_to_ntriples("(a b)").is_err());
+    assert!(text_to_ntriples("(a (b c) d)").is_err());
+    assert_eq!(
+        text_to_ntriples("papa loves mama").unwrap_err().message(),
+        "`papa` is not an absolute IRI"
+    );
+    assert!(lino_to_ntriples(&[triple("http://s", "http://p", "1:o")]).is_err());
+    assert!(lino_to_ntriples(&[triple("\"literal\"", "http://p", "http://o")]).is_err());
+    assert!(lino_to_ntriples(&[triple("http://s", "_:p", "http://o")]).is_err());
+    assert!(lino_to_ntriples(&[triple("http://s", "http://p", "\"unterminated")]).is_err());
+}
+
+#[test]
+fn test_invalid_documents_report_location() {
+    let error = ntriples_to_lino("<http://a> <http://b> <http://c> .\n<http://a> <http://b> .").unwrap_err();
+    assert_eq!(error.line(), Some(2));
+    assert!(ntriples_to_lino("ex:a ex:b ex:c .").is_err());
+    assert!(turtle_to_lino("ex:a ex:b ex:c .").is_err());
+    assert!(turtle_to_lino("@prefix ex: <http://e/> .\nex:a ex:b (ex:c) .").is_err());
+}
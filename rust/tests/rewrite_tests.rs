@@ -0,0 +1,84 @@
+use links_notation::parse_lino_to_links;
+use links_notation::pattern::Pattern;
+use links_notation::rewrite::{Rewriter, Rule};
+use links_notation::LiNo;
+
+#[test]
+fn test_rules_in_lino() {
+    let rewriter = Rewriter::parse("(befriend: ($x: $a knows $b) ($x: $a friend $b))\n(colour color)").unwrap();
+    assert_eq!(rewriter.rules().len(), 2);
+    assert_eq!(rewriter.rules()[0].name.as_deref(), Some("befriend"));
+    assert_eq!(rewriter.rules()[1].name, None);
+
+    let document = "(f1: alice knows bob)\n(f2: bob likes (colour red))\n(f3: bob knows carol)";
+    assert_eq!(
+        rewriter.run_text(document).unwrap(),
+        "(f1: alice friend bob)\n(f2: bob likes (color red))\n(f3: bob friend carol)"
+    );
+}
+
+#[test]
+fn test_trace_is_deterministic() {
+    let rewriter = Rewriter::parse("(a b)\n(b c)").unwrap();
+    let links = parse_lino_to_links("(x a)\n(y (b a))").unwrap();
+    let rewrite = rewriter.run(links).unwrap();
+    assert!(rewrite.fixpoint);
+    let steps: Vec<(usize, Vec<usize>)> = rewrite.trace.iter().map(|applied| (applied.rule, applied.path.clone())).collect();
+    // The first rule is applied everywhere before the second one is considered
+    assert_eq!(
+        steps,
+        vec![
+            (0, vec![0, 1]),
+            (0, vec![1, 1, 1]),
+            (1, vec![0, 1]),
+            (1, vec![1, 1, 0]),
+            (1, vec![1, 1, 1]),
+        ]
+    );
+    assert_eq!(rewrite.trace[0].before, LiNo::Ref("a".to_string()));
+    assert_eq!(rewrite.trace[0].after, LiNo::Ref("b".to_string()));
+    assert_eq!(rewrite.links, parse_lino_to_links("(x c)\n(y (c c))").unwrap());
+}
+
+#[test]
+fn test_max_steps() {
+    let rewriter = Rewriter::parse("(grow: (n $x) (n (s $x)))").unwrap().with_max_steps(3);
+    let rewrite = rewriter.run(parse_lino_to_links("(n z)").unwrap()).unwrap();
+    assert!(!rewrite.fixpoint);
+    assert_eq!(rewrite.trace.len(), 3);
+    assert_eq!(rewrite.links, parse_lino_to_links("(n (s (s (s z))))").unwrap());
+
+    let mut links = parse_lino_to_links("(n z)").unwrap();
+    rewriter.step(&mut links).unwrap().unwrap();
+    assert_eq!(links, parse_lino_to_links("(n (s z))").unwrap());
+}
+
+#[test]
+fn test_rules_that_change_nothing_reach_a_fixpoint() {
+    let rewriter = Rewriter::parse("(same: ($x $y) ($x $y))").unwrap();
+    let rewrite = rewriter.run(parse_lino_to_links("(a b)").unwrap()).unwrap();
+    assert!(rewrite.fixpoint);
+    assert!(rewrite.trace.is_empty());
+}
+
+#[test]
+fn test_sequences_and_ids() {
+    let rewriter = Rewriter::parse("(flatten: ($id: list $items..) ($id: $items..))").unwrap();
+    assert_eq!(rewriter.run_text("(xs: list a b c)\n(ys: list)").unwrap(), "(xs: a b c)\n(ys)");
+
+    let rename = Rule::new(Pattern::parse("(old $x)").unwrap(), parse_lino_to_links("(new $x)").unwrap().remove(0))
+        .unwrap()
+        .with_name("rename");
+    assert_eq!(Rewriter::new(vec![rename]).run_text("(old one)").unwrap(), "(new one)");
+}
+
+#[test]
+fn test_invalid_rules() {
+    assert!(Rewriter::parse("(unbound: (a $x) (b $y))").unwrap_err().message().contains("`$y`"));
+    assert!(Rewriter::parse("(a b c)").is_err());
+    assert!(Rewriter::parse("single").is_err());
+
+    let rewriter = Rewriter::parse("(to-id: ($x b) ($x: b))").unwrap();
+    let error = rewriter.run_text("((a c) b)").unwrap_err();
+    assert!(error.message().starts_with("rule `to-id`"));
+}
@@ -0,0 +1,126 @@
+use links_notation::parse_lino_to_links;
+use links_notation::schema::{Definition, Kind, Schema, Selector, Target};
+
+const SCHEMA: &str = "person: (arity: 1 *) (values: ref name age) (requires: name)
+name: (arity: 1) (values: ref)
+age: (arity: 1) (values: number)
+friend: (arity: 2) (references: *)
+color: (references: red green)";
+
+fn messages(schema: &Schema, document: &str) -> Vec<String> {
+    schema
+        .validate_document(document)
+        .unwrap()
+        .into_iter()
+        .map(|violation| violation.to_string())
+        .collect()
+}
+
+#[test]
+fn test_parse_schema() {
+    let schema = Schema::parse(SCHEMA).unwrap();
+    assert_eq!(schema.definitions().len(), 5);
+    let person = schema.definition_for(Some("person")).unwrap();
+    assert_eq!(person.arity, Some((1, None)));
+    assert_eq!(
+        person.values,
+        Some(vec![Kind::Ref, Kind::Id("name".to_string()), Kind::Id("age".to_string())])
+    );
+    assert_eq!(person.requires, vec!["name"]);
+    let friend = schema.definition_for(Some("friend")).unwrap();
+    assert_eq!(friend.references, Some(vec![Target::Defined]));
+    assert!(schema.definition_for(Some("other")).is_none());
+}
+
+#[test]
+fn test_valid_document() {
+    let schema = Schema::parse(SCHEMA).unwrap();
+    let document = "person: (name: alice) (age: 30)\nperson: (name: bob) engineer\nfriend: name age\ncolor: red";
+    assert_eq!(messages(&schema, document), Vec::<String>::new());
+}
+
+#[test]
+fn test_reports_all_violations() {
+    let schema = Schema::parse(SCHEMA).unwrap();
+    let document = "person: (age: old) (likes: tea)\nfriend: alice\ncolor: blue\npet: cat";
+    assert_eq!(
+        messages(&schema, document),
+        vec![
+            "value 2 of `person` is `(likes: tea)`, expected ref or `name` or `age` at 0 (line 1, column 1)",
+            "`person` is missing required `name` at 0 (line 1, column 1)",
+            "value 1 of `age` is `old`, expected number at 0/0 (line 1, column 9)",
+            "id `likes` is not allowed at 0/1 (line 1, column 20)",
+            "`friend` has 1 values, expected 2 at 1 (line 2, column 1)",
+            "reference `alice` in `friend` does not name an allowed target at 1 (line 2, column 1)",
+            "reference `blue` in `color` does not name an allowed target at 2 (line 3, column 1)",
+            "id `pet` is not allowed at 3 (line 4, column 1)",
+        ]
+    );
+}
+
+#[test]
+fn test_any_and_unnamed_definitions() {
+    let schema = Schema::parse("*: (arity: 0 2)\n_: (values: link)").unwrap();
+    let violations = schema.validate(&parse_lino_to_links("a: b c d\n((x: y) z)").unwrap());
+    let found: Vec<(Vec<usize>, String)> = violations.into_iter().map(|v| (v.path, v.message)).collect();
+    assert_eq!(
+        found,
+        vec![
+            (vec![0], "`a` has 3 values, expected 0 to 2".to_string()),
+            (vec![1], "value 2 of link without id is `z`, expected link".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_schema_errors() {
+    assert_eq!(
+        Schema::parse("a: (arity: 3 1)").unwrap_err().message(),
+        "expected `(arity: n)` or `(arity: min max)`, found `(arity: 3 1)`"
+    );
+    assert_eq!(Schema::parse("a: (size: 1)").unwrap_err().message(), "unknown constraint `size` in `a`");
+    assert_eq!(Schema::parse("a\n").unwrap_err().message(), "expected a definition `id: constraints`, found `a`");
+    assert!(Schema::new(vec![Definition::new(Selector::Any), Definition::new(Selector::Any)]).is_err());
+}
+
+#[test]
+fn test_violation_locations() {
+    let schema = Schema::parse(SCHEMA).unwrap();
+    let document = "person:\n  alice\n  (name: a b)\nfriend: (x y)\n  pet: cat";
+    let violations = schema.validate_document(document).unwrap();
+    let found: Vec<(&str, Option<(usize, usize)>)> =
+        violations.iter().map(|v| (v.message.as_str(), v.location)).collect();
+    assert_eq!(
+        found,
+        vec![
+            ("`name` has 2 values, expected 1", Some((3, 3))),
+            ("`friend` has 1 values, expected 2", Some((4, 1))),
+            ("`friend` has 1 values, expected 2", Some((4, 1))),
+            ("id `pet` is not allowed", Some((5, 3))),
+        ]
+    );
+    assert!(schema.validate(&parse_lino_to_links(document).unwrap())[0].location.is_none());
+}
+
+#[test]
+fn test_numbers_are_finite() {
+    let schema = Schema::parse(SCHEMA).unwrap();
+    assert_eq!(messages(&schema, "person: (name: a) (age: -1.5e3)"), Vec::<String>::new());
+    for age in ["NaN", "inf", "-infinity", "Infinity"] {
+        let document = format!("person: (name: a) (age: {})", age);
+        assert_eq!(messages(&schema, &document).len(), 1, "{}", age);
+    }
+}
+
+#[test]
+fn test_keyword_ids() {
+    let schema = Schema::parse("pair: (values: (id: ref) number)\nref: (arity: 1)").unwrap();
+    let pair = schema.definition_for(Some("pair")).unwrap();
+    assert_eq!(pair.values, Some(vec![Kind::Id("ref".to_string()), Kind::Number]));
+    assert_eq!(messages(&schema, "pair: (ref: x) 1"), Vec::<String>::new());
+    assert_eq!(
+        messages(&schema, "pair: x"),
+        vec!["value 1 of `pair` is `x`, expected `ref` or number at 0 (line 1, column 1)"]
+    );
+    assert!(Schema::parse("pair: (values: (id: a b))").is_err());
+}
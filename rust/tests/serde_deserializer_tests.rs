@@ -0,0 +1,173 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use links_notation::{from_str, to_string};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    server: Server,
+    tags: Vec<String>,
+    timeout: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Line(i32, i32),
+    Rect { width: u32, height: u32 },
+}
+
+#[test]
+fn test_deserialize_scalars() {
+    assert_eq!(from_str::<i32>("42").unwrap(), 42);
+    assert!(from_str::<bool>("true").unwrap());
+    assert_eq!(from_str::<f64>("1.5").unwrap(), 1.5);
+    assert_eq!(from_str::<String>("'has space'").unwrap(), "has space");
+    assert_eq!(from_str::<char>("c").unwrap(), 'c');
+}
+
+#[test]
+fn test_deserialize_struct_from_lines() {
+    let point: Point = from_str("x: 1\ny: 2").unwrap();
+    assert_eq!(point, Point { x: 1, y: 2 });
+}
+
+#[test]
+fn test_deserialize_struct_from_parenthesized_links() {
+    let point: Point = from_str("(x: 1) (y: 2)").unwrap();
+    assert_eq!(point, Point { x: 1, y: 2 });
+}
+
+#[test]
+fn test_deserialize_indented_blocks() {
+    let input = "name: 'my service'
+server:
+  host: localhost
+  port: 8080
+tags:
+  web
+  api
+timeout: 30";
+    let config: Config = from_str(input).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "my service".to_string(),
+            server: Server { host: "localhost".to_string(), port: 8080 },
+            tags: vec!["web".to_string(), "api".to_string()],
+            timeout: Some(30),
+        }
+    );
+}
+
+#[test]
+fn test_deserialize_inline_values() {
+    let input = "name: svc
+server: (host: localhost) (port: 80)
+tags: web api
+timeout: ()";
+    let config: Config = from_str(input).unwrap();
+    assert_eq!(config.server, Server { host: "localhost".to_string(), port: 80 });
+    assert_eq!(config.tags, vec!["web".to_string(), "api".to_string()]);
+    assert_eq!(config.timeout, None);
+}
+
+#[test]
+fn test_deserialize_single_reference_as_sequence() {
+    let input = "name: svc\nserver: ((host: h) (port: 1))\ntags: web\ntimeout: ()";
+    let config: Config = from_str(input).unwrap();
+    assert_eq!(config.tags, vec!["web".to_string()]);
+}
+
+#[test]
+fn test_deserialize_sequences() {
+    assert_eq!(from_str::<Vec<i32>>("1\n2\n3").unwrap(), vec![1, 2, 3]);
+    assert_eq!(from_str::<Vec<Vec<i32>>>("1 2\n3 4").unwrap(), vec![vec![1, 2], vec![3, 4]]);
+    assert_eq!(from_str::<(i32, String)>("1\nsecond").unwrap(), (1, "second".to_string()));
+    assert_eq!(from_str::<Vec<i32>>("").unwrap(), Vec::<i32>::new());
+}
+
+#[test]
+fn test_deserialize_map() {
+    let map: BTreeMap<String, i32> = from_str("alpha: 1\n'beta gamma': 2").unwrap();
+    assert_eq!(map.get("alpha"), Some(&1));
+    assert_eq!(map.get("beta gamma"), Some(&2));
+
+    let map: BTreeMap<u32, String> = from_str("1: one\n2: two").unwrap();
+    assert_eq!(map.get(&2), Some(&"two".to_string()));
+}
+
+#[test]
+fn test_deserialize_enums() {
+    assert_eq!(from_str::<Shape>("Empty").unwrap(), Shape::Empty);
+    assert_eq!(from_str::<Shape>("Circle: 2.5").unwrap(), Shape::Circle(2.5));
+    assert_eq!(from_str::<Shape>("(Line: 1 2)").unwrap(), Shape::Line(1, 2));
+    assert_eq!(
+        from_str::<Shape>("Rect: (width: 3) (height: 4)").unwrap(),
+        Shape::Rect { width: 3, height: 4 }
+    );
+    assert_eq!(
+        from_str::<Shape>("Rect:\n  width: 3\n  height: 4").unwrap(),
+        Shape::Rect { width: 3, height: 4 }
+    );
+    assert_eq!(
+        from_str::<Vec<Shape>>("Empty\nCircle: 1").unwrap(),
+        vec![Shape::Empty, Shape::Circle(1.0)]
+    );
+}
+
+#[test]
+fn test_round_trip_through_serializer() {
+    let config = Config {
+        name: "it's mine".to_string(),
+        server: Server { host: "example.com".to_string(), port: 443 },
+        tags: vec![],
+        timeout: Some(5),
+    };
+    let text = to_string(&config).unwrap();
+    assert_eq!(from_str::<Config>(&text).unwrap(), config);
+
+    let shapes = vec![Shape::Empty, Shape::Line(-1, 1), Shape::Rect { width: 1, height: 2 }];
+    assert_eq!(from_str::<Vec<Shape>>(&to_string(&shapes).unwrap()).unwrap(), shapes);
+
+    let nested = vec![vec![1], vec![2, 3]];
+    assert_eq!(from_str::<Vec<Vec<i32>>>(&to_string(&nested).unwrap()).unwrap(), nested);
+}
+
+#[test]
+fn test_error_reports_line_and_column() {
+    let error = from_str::<Point>("x: 1\ny: abc").unwrap_err();
+    assert_eq!(error.line(), Some(2));
+    assert_eq!(error.column(), Some(4));
+    assert_eq!(error.to_string(), "invalid i32 `abc` at line 2, column 4");
+}
+
+#[test]
+fn test_error_for_missing_field_points_to_struct() {
+    let error = from_str::<Config>("name: svc\nserver:\n  host: localhost\ntags: ()\ntimeout: ()").unwrap_err();
+    assert!(error.message().contains("port"));
+    assert_eq!(error.line(), Some(3));
+}
+
+#[test]
+fn test_error_for_invalid_syntax() {
+    let error = from_str::<Point>("x: 1\n(y: 2").unwrap_err();
+    assert!(error.to_string().starts_with("Parse error"));
+    assert_eq!(error.line(), Some(2));
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use links_notation::{parse_lino_to_links, to_lino, to_string, LiNo};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Serialize)]
+struct Service {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+    origin: Point,
+    timeout: Option<u32>,
+}
+
+#[derive(Serialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Line(i32, i32),
+    Rect { width: u32, height: u32 },
+}
+
+#[test]
+fn test_serialize_scalars() {
+    assert_eq!(to_string(&42).unwrap(), "42");
+    assert_eq!(to_string(&true).unwrap(), "true");
+    assert_eq!(to_string(&1.5).unwrap(), "1.5");
+    assert_eq!(to_string("simple").unwrap(), "simple");
+}
+
+#[test]
+fn test_serialize_quotes_strings_when_needed() {
+    assert_eq!(to_string("has space").unwrap(), "'has space'");
+    assert_eq!(to_string("key:value").unwrap(), "'key:value'");
+    assert_eq!(to_string("it's").unwrap(), "it's");
+    assert_eq!(to_string("'tis").unwrap(), "\"'tis\"");
+}
+
+#[test]
+fn test_serialize_string_with_both_quotes_fails() {
+    assert!(to_string("it's \"quoted\"").is_err());
+}
+
+#[test]
+fn test_serialize_struct_to_field_links() {
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(
+        to_lino(&point).unwrap(),
+        LiNo::Link {
+            id: None,
+            values: vec![
+                LiNo::Link { id: Some("x".to_string()), values: vec![LiNo::Ref("1".to_string())] },
+                LiNo::Link { id: Some("y".to_string()), values: vec![LiNo::Ref("2".to_string())] },
+            ],
+        }
+    );
+    assert_eq!(to_string(&point).unwrap(), "x: 1\ny: 2");
+}
+
+#[test]
+fn test_serialize_nested_struct() {
+    let service = Service {
+        name: "my service".to_string(),
+        port: 8080,
+        tags: vec!["web".to_string(), "api".to_string()],
+        origin: Point { x: 0, y: -1 },
+        timeout: None,
+    };
+    let output = to_string(&service).unwrap();
+    assert_eq!(
+        output,
+        "name: 'my service'\nport: 8080\ntags: (web api)\norigin: ((x: 0) (y: -1))\ntimeout: ()"
+    );
+
+    // The output is valid Links Notation
+    let links = parse_lino_to_links(&output).unwrap();
+    assert_eq!(links.len(), 5);
+}
+
+#[test]
+fn test_serialize_sequence() {
+    assert_eq!(to_string(&vec![1, 2, 3]).unwrap(), "1\n2\n3");
+    assert_eq!(to_string(&vec![vec![1, 2], vec![3, 4]]).unwrap(), "1 2\n3 4");
+    assert_eq!(
+        to_lino(&(1, "a")).unwrap(),
+        LiNo::Link { id: None, values: vec![LiNo::Ref("1".to_string()), LiNo::Ref("a".to_string())] }
+    );
+}
+
+#[test]
+fn test_serialize_map() {
+    let mut map = BTreeMap::new();
+    map.insert("alpha", 1);
+    map.insert("beta gamma", 2);
+    assert_eq!(to_string(&map).unwrap(), "alpha: 1\n'beta gamma': 2");
+}
+
+#[test]
+fn test_serialize_map_with_non_scalar_key_fails() {
+    let mut map = BTreeMap::new();
+    map.insert(vec![1, 2], 1);
+    assert!(to_string(&map).is_err());
+}
+
+#[test]
+fn test_serialize_enums() {
+    assert_eq!(to_string(&Shape::Empty).unwrap(), "Empty");
+    assert_eq!(to_string(&Shape::Circle(2.5)).unwrap(), "Circle: 2.5");
+    assert_eq!(to_string(&Shape::Line(1, 2)).unwrap(), "Line: 1 2");
+    assert_eq!(
+        to_string(&Shape::Rect { width: 3, height: 4 }).unwrap(),
+        "Rect: (width: 3) (height: 4)"
+    );
+    assert_eq!(
+        to_string(&vec![Shape::Empty, Shape::Circle(1.0)]).unwrap(),
+        "Empty\nCircle: 1"
+    );
+}
+
+#[test]
+fn test_serialize_option_and_unit() {
+    assert_eq!(to_string(&Some(5)).unwrap(), "5");
+    assert_eq!(to_string(&None::<i32>).unwrap(), "");
+    assert_eq!(to_lino(&()).unwrap(), LiNo::Link { id: None, values: vec![] });
+}
+
+#[test]
+fn test_empty_values_share_the_empty_link() {
+    let empty = LiNo::Link { id: None, values: vec![] };
+    assert_eq!(to_lino(&None::<i32>).unwrap(), empty);
+    assert_eq!(to_lino(&()).unwrap(), empty);
+    assert_eq!(to_lino("").unwrap(), empty);
+    assert_eq!(to_lino(&Vec::<i32>::new()).unwrap(), empty);
+    assert_eq!(to_lino(&BTreeMap::<String, i32>::new()).unwrap(), empty);
+}
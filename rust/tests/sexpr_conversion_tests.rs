@@ -0,0 +1,109 @@
+use links_notation::sexpr::{lino_to_sexpr, links_to_sexpr, sexpr_to_lino, sexpr_to_text, text_to_sexpr};
+use links_notation::LiNo;
+
+fn reference(value: &str) -> LiNo<String> {
+    LiNo::Ref(value.to_string())
+}
+
+fn assert_round_trip(document: &str) {
+    let links = sexpr_to_lino(document).unwrap();
+    assert_eq!(links_to_sexpr(&links), document, "round trip of {:?}", document);
+}
+
+#[test]
+fn test_atoms_and_strings_become_references() {
+    assert_eq!(
+        sexpr_to_lino("atom 42 \"some text\"").unwrap(),
+        vec![reference("atom"), reference("42"), reference("some text")]
+    );
+}
+
+#[test]
+fn test_nested_lists() {
+    assert_eq!(
+        sexpr_to_lino("(a (b c) ())").unwrap(),
+        vec![LiNo::Link {
+            id: None,
+            values: vec![
+                reference("a"),
+                LiNo::Link { id: None, values: vec![reference("b"), reference("c")] },
+                LiNo::Link { id: None, values: vec![] },
+            ]
+        }]
+    );
+}
+
+#[test]
+fn test_named_links() {
+    assert_eq!(
+        sexpr_to_lino("(papa : loves mama)").unwrap(),
+        vec![LiNo::Link {
+            id: Some("papa".to_string()),
+            values: vec![reference("loves"), reference("mama")]
+        }]
+    );
+    assert_eq!(text_to_sexpr("(papa: loves mama)").unwrap(), "(papa : loves mama)");
+    assert_eq!(sexpr_to_text("(papa : loves mama)").unwrap(), "(papa: loves mama)");
+}
+
+#[test]
+fn test_quoted_markers_are_plain_references() {
+    assert_eq!(
+        sexpr_to_lino("(a \":\" \".\" b)").unwrap(),
+        vec![LiNo::Link {
+            id: None,
+            values: vec![reference("a"), reference(":"), reference("."), reference("b")]
+        }]
+    );
+    assert_eq!(lino_to_sexpr(&LiNo::Ref(":")), "\":\"");
+}
+
+#[test]
+fn test_dotted_pairs() {
+    assert_eq!(
+        sexpr_to_lino("(a . b)").unwrap(),
+        vec![LiNo::Link { id: None, values: vec![reference("a"), reference("."), reference("b")] }]
+    );
+    assert_eq!(text_to_sexpr("(a b . c)").unwrap(), "(a b . c)");
+    assert_eq!(text_to_sexpr("(a . b c)").unwrap(), "(a \".\" b c)");
+}
+
+#[test]
+fn test_strings_are_escaped() {
+    let link = LiNo::Link { id: None, values: vec![reference("say \"hi\"\n"), reference("a\\b")] };
+    assert_eq!(lino_to_sexpr(&link), "(\"say \\\"hi\\\"\\n\" \"a\\\\b\")");
+    assert_eq!(sexpr_to_lino(&lino_to_sexpr(&link)).unwrap(), vec![link]);
+}
+
+#[test]
+fn test_comments_are_skipped() {
+    assert_eq!(
+        sexpr_to_lino("; heading\n(a ; inline\n b)").unwrap(),
+        sexpr_to_lino("(a b)").unwrap()
+    );
+}
+
+#[test]
+fn test_invalid_input_reports_location() {
+    let error = sexpr_to_lino("(a\n  (b c)").unwrap_err();
+    assert_eq!((error.line(), error.column()), (Some(1), Some(1)));
+
+    let error = sexpr_to_lino("(a b)\n)").unwrap_err();
+    assert_eq!((error.line(), error.column()), (Some(2), Some(1)));
+
+    assert!(sexpr_to_lino("(. a)").is_err());
+    assert!(sexpr_to_lino("(a .)").is_err());
+    assert!(sexpr_to_lino("(a . b c)").is_err());
+    assert!(sexpr_to_lino("\"open").is_err());
+}
+
+#[test]
+fn test_round_trips() {
+    assert_round_trip("atom");
+    assert_round_trip("\"two words\"");
+    assert_round_trip("()");
+    assert_round_trip("(a (b (c)) \"d e\")");
+    assert_round_trip("(id : a b)\n(id :)");
+    assert_round_trip("(a b . c)\n((a . b) . (c . d))");
+    assert_round_trip("(\"it's\" \"both ' and \\\"\")");
+}
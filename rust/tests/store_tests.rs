@@ -0,0 +1,128 @@
+use links_notation::parse_lino_to_links;
+use links_notation::store::{LinkStore, StoredLink};
+
+#[test]
+fn test_ingest_assigns_addresses() {
+    let mut store = LinkStore::new();
+    let roots = store.ingest_document("(papa: loves mama)\n(mama: loves papa)").unwrap();
+    assert_eq!(roots, vec![3, 2]);
+    assert_eq!(store.len(), 3);
+    assert_eq!(store.address_of("loves"), Some(1));
+    assert_eq!(store.name_of(2), Some("mama"));
+    assert_eq!(
+        store.get(3),
+        Some(&StoredLink {
+            address: 3,
+            name: Some("papa".to_string()),
+            values: vec![1, 2],
+        })
+    );
+    assert_eq!(store.get(2).unwrap().values, vec![1, 3]);
+    assert_eq!(store.get(0), None);
+}
+
+#[test]
+fn test_identical_links_are_stored_once() {
+    let mut store = LinkStore::new();
+    let roots = store.ingest_document("(a b)\n(a b)\n(c (a b))\n(x: a b)").unwrap();
+    assert_eq!(roots[0], roots[1]);
+    assert_eq!(store.get(roots[2]).unwrap().values[1], roots[0]);
+    assert_ne!(roots[3], roots[0]);
+    assert_eq!(store.create(vec![1, 2]).unwrap(), roots[0]);
+}
+
+#[test]
+fn test_redefinition() {
+    let mut store = LinkStore::new();
+    store.ingest_document("(a: b c)\n(a: b c)").unwrap();
+    assert_eq!(store.len(), 3);
+    let error = store.ingest_document("(a: c b)").unwrap_err();
+    assert!(error.message().contains("already defined"));
+}
+
+#[test]
+fn test_create_update_delete() {
+    let mut store = LinkStore::new();
+    let source = store.point("source");
+    let target = store.point("target");
+    let link = store.create(vec![source, target]).unwrap();
+    assert!(store.create(vec![source, 99]).is_err());
+
+    let other = store.create(vec![target, source]).unwrap();
+    assert!(store.update(link, vec![target, source]).is_err());
+    store.update(link, vec![source, source]).unwrap();
+    assert_eq!(store.get(link).unwrap().values, vec![source, source]);
+    assert_eq!(store.create(vec![source, source]).unwrap(), link);
+
+    assert!(store.delete(source).is_err());
+    assert_eq!(store.delete(link).unwrap().values, vec![source, source]);
+    store.delete(other).unwrap();
+    store.delete(source).unwrap();
+    assert_eq!(store.get(link), None);
+    assert_eq!(store.address_of("source"), None);
+    assert_eq!(store.len(), 1);
+    assert!(store.delete(link).is_err());
+    assert_eq!(store.point("source"), 5);
+}
+
+#[test]
+fn test_update_rejects_unnamed_cycles() {
+    let mut store = LinkStore::new();
+    let point = store.point("point");
+    let a = store.create(vec![point]).unwrap();
+    let b = store.create(vec![a]).unwrap();
+    let named = store.create_named("named", vec![b]).unwrap();
+
+    let error = store.update(a, vec![b]).unwrap_err();
+    assert_eq!(error.message(), format!("link {} would contain itself", a));
+    assert!(store.update(a, vec![a]).is_err());
+    assert_eq!(store.get(a).unwrap().values, vec![point]);
+
+    // Named links break cycles, since they are exported as references
+    store.update(a, vec![named]).unwrap();
+    assert_eq!(store.to_text(), "point\n(named: ((named)))");
+}
+
+#[test]
+fn test_queries() {
+    let mut store = LinkStore::new();
+    store
+        .ingest_document("(1: papa loves mama)\n(2: son loves mama)\n(3: papa loves son)\n(papa mama)")
+        .unwrap();
+    let papa = store.address_of("papa").unwrap();
+    let mama = store.address_of("mama").unwrap();
+    let one = store.address_of("1").unwrap();
+    let three = store.address_of("3").unwrap();
+    assert_eq!(store.query(&[Some(papa), None, None]), vec![one, three]);
+    assert_eq!(store.query_names(&[None, Some("loves"), Some("mama")]).len(), 2);
+    assert_eq!(store.query(&[Some(papa), None]), vec![store.create(vec![papa, mama]).unwrap()]);
+    assert_eq!(store.query(&[]), vec![papa, store.address_of("loves").unwrap(), mama, store.address_of("son").unwrap()]);
+    assert!(store.query_names(&[Some("nobody"), None]).is_empty());
+}
+
+#[test]
+fn test_export() {
+    let mut store = LinkStore::new();
+    let document = "(papa: loves mama)\nalone\n(a (b c) ())\n(x: 'with space')";
+    store.ingest_document(document).unwrap();
+    assert_eq!(store.to_text(), document);
+
+    let mut copy = LinkStore::new();
+    copy.ingest(&store.to_lino()).unwrap();
+    assert_eq!(copy.to_lino(), parse_lino_to_links(document).unwrap());
+}
+
+#[test]
+fn test_export_keeps_top_level_links_that_are_also_nested() {
+    let mut store = LinkStore::new();
+    let document = "(a b)\n(c (a b))\nd\n(e d)";
+    store.ingest_document(document).unwrap();
+    assert_eq!(store.to_text(), document);
+
+    let mut copy = LinkStore::new();
+    copy.ingest(&store.to_lino()).unwrap();
+    assert_eq!(copy.to_lino(), parse_lino_to_links(document).unwrap());
+
+    let loaded = LinkStore::from_bytes(&store.to_bytes().unwrap()).unwrap();
+    assert_eq!(loaded.to_text(), document);
+}
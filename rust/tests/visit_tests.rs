@@ -0,0 +1,138 @@
+use links_notation::visit::{breadth_first, post_order, pre_order, walk_link, walk_lino_mut, Fold, Visitor, VisitorMut};
+use links_notation::{parse_lino, parse_lino_to_links, LiNo};
+
+const DOCUMENT: &str = "(papa: loves mama)\n(son: (likes ball) tea)";
+
+fn texts<'a>(nodes: impl Iterator<Item = links_notation::visit::Node<'a, String>>) -> Vec<String> {
+    nodes.map(|node| node.lino.format(true)).collect()
+}
+
+#[derive(Default)]
+struct Names {
+    ids: Vec<String>,
+    references: Vec<String>,
+    links: usize,
+}
+
+impl Visitor<String> for Names {
+    fn visit_link(&mut self, id: Option<&String>, values: &[LiNo<String>]) {
+        self.links += 1;
+        walk_link(self, id, values);
+    }
+
+    fn visit_id(&mut self, id: &String) {
+        self.ids.push(id.clone());
+    }
+
+    fn visit_ref(&mut self, value: &String) {
+        self.references.push(value.clone());
+    }
+}
+
+#[test]
+fn test_visitor() {
+    let document = parse_lino(DOCUMENT).unwrap();
+    let mut names = Names::default();
+    document.accept(&mut names);
+    assert_eq!(names.ids, vec!["papa", "son"]);
+    assert_eq!(names.references, vec!["loves", "mama", "likes", "ball", "tea"]);
+    assert_eq!(names.links, 4);
+}
+
+struct Rename;
+
+impl VisitorMut<String> for Rename {
+    fn visit_lino_mut(&mut self, lino: &mut LiNo<String>) {
+        // Drop nested links entirely, keeping only their first value
+        if let LiNo::Link { id: None, values } = lino {
+            if let Some(first) = values.first().cloned() {
+                *lino = first;
+            }
+        }
+        walk_lino_mut(self, lino);
+    }
+
+    fn visit_ref_mut(&mut self, value: &mut String) {
+        *value = value.to_uppercase();
+    }
+}
+
+#[test]
+fn test_visitor_mut() {
+    let mut links = parse_lino_to_links(DOCUMENT).unwrap();
+    for link in &mut links {
+        link.accept_mut(&mut Rename);
+    }
+    assert_eq!(links, parse_lino_to_links("(papa: LOVES MAMA)\n(son: LIKES TEA)").unwrap());
+}
+
+struct Depth;
+
+impl<T> Fold<T> for Depth {
+    type Output = usize;
+
+    fn fold_ref(&mut self, _value: T) -> usize {
+        0
+    }
+
+    fn fold_link(&mut self, _id: Option<T>, values: Vec<usize>) -> usize {
+        values.into_iter().max().unwrap_or(0) + 1
+    }
+}
+
+struct Lengths;
+
+impl Fold<String> for Lengths {
+    type Output = LiNo<usize>;
+
+    fn fold_ref(&mut self, value: String) -> LiNo<usize> {
+        LiNo::Ref(value.len())
+    }
+
+    fn fold_link(&mut self, id: Option<String>, values: Vec<LiNo<usize>>) -> LiNo<usize> {
+        LiNo::Link {
+            id: id.map(|id| id.len()),
+            values,
+        }
+    }
+}
+
+#[test]
+fn test_fold() {
+    let document = parse_lino(DOCUMENT).unwrap();
+    assert_eq!(document.clone().fold(&mut Depth), 3);
+    let lengths = parse_lino_to_links("(papa: loves mama)").unwrap().remove(0).fold(&mut Lengths);
+    assert_eq!(lengths.to_string(), "(4: 5 4)");
+}
+
+#[test]
+fn test_iterators() {
+    let links = parse_lino_to_links(DOCUMENT).unwrap();
+    assert_eq!(
+        texts(pre_order(&links)),
+        vec!["papa: loves mama", "loves", "mama", "son: (likes ball) tea", "likes ball", "likes", "ball", "tea"]
+    );
+    assert_eq!(
+        texts(post_order(&links)),
+        vec!["loves", "mama", "papa: loves mama", "likes", "ball", "likes ball", "tea", "son: (likes ball) tea"]
+    );
+    assert_eq!(
+        texts(breadth_first(&links)),
+        vec!["papa: loves mama", "son: (likes ball) tea", "loves", "mama", "likes ball", "tea", "likes", "ball"]
+    );
+}
+
+#[test]
+fn test_depth_and_path() {
+    let links = parse_lino_to_links(DOCUMENT).unwrap();
+    let ball = pre_order(&links).find(|node| node.lino == &LiNo::Ref("ball".to_string())).unwrap();
+    assert_eq!(ball.depth, 2);
+    assert_eq!(ball.path, vec![1, 0, 1]);
+
+    let son = &links[1];
+    let nodes: Vec<(usize, Vec<usize>)> = son.post_order().map(|node| (node.depth, node.path)).collect();
+    assert_eq!(nodes, vec![(2, vec![0, 0]), (2, vec![0, 1]), (1, vec![0]), (1, vec![1]), (0, vec![])]);
+    assert_eq!(son.breadth_first().map(|node| node.depth).collect::<Vec<_>>(), vec![0, 1, 1, 2, 2]);
+    assert_eq!(son.pre_order().count(), 5);
+    assert_eq!(LiNo::Ref("alone").pre_order().count(), 1);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "xml")]
+
+use links_notation::xml::{lino_to_xml, text_to_xml, xml_to_lino, xml_to_text};
+use links_notation::LiNo;
+
+fn assert_round_trip(xml: &str) {
+    let lino = xml_to_lino(xml).unwrap();
+    assert_eq!(lino_to_xml(&lino).unwrap(), xml, "LiNo round trip of {:?}", xml);
+
+    let text = xml_to_text(xml).unwrap();
+    assert_eq!(text_to_xml(&text).unwrap(), xml, "text round trip of {:?} via {:?}", xml, text);
+}
+
+#[test]
+fn test_elements_become_links() {
+    assert_eq!(
+        xml_to_lino("<title>Hello</title>").unwrap(),
+        LiNo::Link {
+            id: Some("title".to_string()),
+            values: vec![LiNo::Ref("Hello".to_string())]
+        }
+    );
+    assert_eq!(xml_to_text("<br/>").unwrap(), "br: ()");
+}
+
+#[test]
+fn test_attributes_and_mixed_content() {
+    assert_eq!(
+        xml_to_text("<p class=\"intro\">Hi <b>there</b>!</p>").unwrap(),
+        "p: (@class: intro) Hi (b: there) !"
+    );
+    assert_eq!(xml_to_text("<a href=\"\"/>").unwrap(), "a: (@href: ())");
+}
+
+#[test]
+fn test_ids_and_idrefs() {
+    let xml = r#"<people>
+  <person id="john" friend-ref="jane"/>
+  <person id="jane" friend-ref="john"/>
+</people>"#;
+    assert_eq!(
+        xml_to_text(xml).unwrap(),
+        "people: (john: (person: (@friend-ref: jane))) (jane: (person: (@friend-ref: john)))"
+    );
+}
+
+#[test]
+fn test_idrefs_are_split() {
+    assert_eq!(
+        xml_to_text("<group idrefs=\"a b\" member-refs=\"c  d\" title=\"e f\"/>").unwrap(),
+        "group: (@idrefs: a b) (@member-refs: c d) (@title: 'e f')"
+    );
+}
+
+#[test]
+fn test_single_child_is_marked() {
+    assert_eq!(xml_to_text("<list><item/></list>").unwrap(), "list: () (item: ())");
+    assert_eq!(text_to_xml("list: () (item: ())").unwrap(), "<list><item/></list>");
+    assert_eq!(text_to_xml("list: (item: ())").unwrap(), "<item id=\"list\"/>");
+}
+
+#[test]
+fn test_text_is_trimmed_and_unescaped() {
+    let xml = "<?xml version=\"1.0\"?>\n<!-- note -->\n<a>\n  x &amp; y <![CDATA[<z>]]>\n</a>\n";
+    assert_eq!(xml_to_lino(xml).unwrap(), xml_to_lino("<a>x &amp; y &lt;z&gt;</a>").unwrap());
+    assert_eq!(text_to_xml("a: 'x & y <z>'").unwrap(), "<a>x &amp; y &lt;z&gt;</a>");
+}
+
+#[test]
+fn test_namespaced_names() {
+    assert_eq!(
+        xml_to_text("<svg:rect xml:lang=\"en\"/>").unwrap(),
+        "'svg:rect': ('@xml:lang': en)"
+    );
+}
+
+#[test]
+fn test_invalid_documents_are_errors() {
+    let error = xml_to_lino("<a>\n  <b></c>\n</a>").unwrap_err();
+    assert_eq!(error.line(), Some(2));
+    assert!(xml_to_lino("<a/><b/>").is_err());
+    assert!(xml_to_lino("<a>").is_err());
+    assert!(xml_to_lino("").is_err());
+}
+
+#[test]
+fn test_invalid_links_are_errors() {
+    assert!(text_to_xml("a b").is_err());
+    assert!(text_to_xml("'not a name': x").is_err());
+    assert!(text_to_xml("a: (@b: (c: d))").is_err());
+    let error = text_to_xml("p: (a b)").unwrap_err();
+    assert_eq!(error.message(), "`(a b)` in element `p` is not text, an element or an attribute");
+    assert!(text_to_xml("p: x (q: (a b))").is_err());
+    assert_eq!(text_to_xml("br: ()").unwrap(), "<br/>");
+}
+
+#[test]
+fn test_round_trips() {
+    assert_round_trip("<a/>");
+    assert_round_trip("<a>text</a>");
+    assert_round_trip("<a b=\"1\" c=\"\"/>");
+    assert_round_trip("<a id=\"x\"><b id=\"y\"/></a>");
+    assert_round_trip("<a><b><c/></b></a>");
+    assert_round_trip("<a>one<b/>two<c>three</c></a>");
+    assert_round_trip("<people><person id=\"john\" friend-ref=\"jane\"/><person id=\"jane\" friend-ref=\"john\"/></people>");
+    assert_round_trip("<a title=\"&lt;&amp;&quot;\">it's</a>");
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "yaml")]
+
+use links_notation::yaml::{lino_to_yaml, text_to_yaml, yaml_to_lino, yaml_to_text};
+use links_notation::LiNo;
+
+fn assert_round_trip(yaml: &str) {
+    let lino = yaml_to_lino(yaml).unwrap();
+    let exported = lino_to_yaml(&lino).unwrap();
+    assert_eq!(yaml_to_lino(&exported).unwrap(), lino, "LiNo round trip of {:?} via {:?}", yaml, exported);
+
+    let text = yaml_to_text(yaml).unwrap();
+    let exported = text_to_yaml(&text).unwrap();
+    assert_eq!(yaml_to_lino(&exported).unwrap(), lino, "text round trip of {:?} via {:?}", yaml, text);
+}
+
+#[test]
+fn test_scalars_become_references() {
+    assert_eq!(yaml_to_lino("hello").unwrap(), LiNo::Ref("hello".to_string()));
+    assert_eq!(yaml_to_lino("~").unwrap(), LiNo::Ref("~".to_string()));
+    assert_eq!(yaml_to_lino("0x1F").unwrap(), LiNo::Ref("0x1F".to_string()));
+    assert_eq!(yaml_to_lino("").unwrap(), LiNo::Ref("null".to_string()));
+}
+
+#[test]
+fn test_quoted_literals_are_tagged() {
+    assert_eq!(yaml_to_text("'42'").unwrap(), "string: 42");
+    assert_eq!(yaml_to_text("\"true\"").unwrap(), "string: true");
+    assert_eq!(yaml_to_text("''").unwrap(), "string: ()");
+}
+
+#[test]
+fn test_mapping_to_text() {
+    let yaml = "name: my service\nport: 8080\ntags:\n  - web\n  - api\n";
+    assert_eq!(yaml_to_text(yaml).unwrap(), "name: 'my service'\nport: 8080\ntags: (web api)");
+}
+
+#[test]
+fn test_anchors_and_aliases() {
+    let yaml = "base: &defaults\n  replicas: 2\nweb: *defaults\n";
+    assert_eq!(yaml_to_text(yaml).unwrap(), "base: (defaults: ((replicas: 2)))\nweb: defaults");
+    assert_eq!(
+        lino_to_yaml(&yaml_to_lino(yaml).unwrap()).unwrap(),
+        "base: &defaults\n  replicas: 2\nweb: *defaults\n"
+    );
+}
+
+#[test]
+fn test_strings_matching_anchor_names_are_tagged() {
+    let yaml = "first: &name value\nsecond: *name\nthird: name\n";
+    assert_eq!(
+        yaml_to_text(yaml).unwrap(),
+        "first: (name: value)\nsecond: name\nthird: (string: name)"
+    );
+}
+
+#[test]
+fn test_reserved_and_redefined_anchors_are_renamed() {
+    let yaml = "- &string a\n- *string\n- &x b\n- &x c\n- *x\n";
+    assert_eq!(yaml_to_text(yaml).unwrap(), "string_2: a\nstring_2\nx: b\nx_2: c\nx_2");
+}
+
+#[test]
+fn test_text_to_yaml() {
+    let yaml = text_to_yaml("name: svc\nports: (80 443)\nlabels: ((app: web))").unwrap();
+    assert_eq!(yaml, "name: svc\nports:\n  - 80\n  - 443\nlabels:\n  app: web\n");
+}
+
+#[test]
+fn test_nested_sequences_are_compact() {
+    let yaml = text_to_yaml("((a: 1) (b: 2))\n(1 2)").unwrap();
+    assert_eq!(yaml, "- a: 1\n  b: 2\n- - 1\n  - 2\n");
+}
+
+#[test]
+fn test_aliases_of_anchored_keys() {
+    let lino = yaml_to_lino("&k key: 1\nother: *k\n").unwrap();
+    assert_eq!(lino.format(false), "((key: 1) (other: key))");
+}
+
+#[test]
+fn test_reference_before_anchor_is_an_error() {
+    assert!(text_to_yaml("first: x\nsecond: (x: value)").is_err());
+}
+
+#[test]
+fn test_unsupported_documents_are_errors() {
+    assert!(yaml_to_lino("a\n---\nb\n").is_err());
+    assert!(yaml_to_lino("? [a, b]\n: c\n").is_err());
+
+    let error = yaml_to_lino("key: [unclosed\n").unwrap_err();
+    assert!(error.line().is_some());
+}
+
+#[test]
+fn test_round_trips() {
+    assert_round_trip("plain");
+    assert_round_trip("'with: colon'");
+    assert_round_trip("\"multi\\nline\"");
+    assert_round_trip("[]");
+    assert_round_trip("{}");
+    assert_round_trip("[[], {}, [1], [a: 1]]");
+    assert_round_trip("- [1, 2]\n- {a: {b: [x, 'null']}}\n");
+    assert_round_trip("string: x\narray: [1, 2]\n'true': yes\n");
+    assert_round_trip("anchored: &list [1, 2]\nagain: *list\nmore: &scalar s\nlast: [*scalar, *list]\n");
+    assert_round_trip("- &item {a: 1}\n- *item\n");
+    assert_round_trip("&root {a: 1}");
+}